@@ -3,7 +3,25 @@
 /// Maximum percentage value (100% = 10000, 2 decimal places)
 pub static MAX_PERCENTAGE: u64 = 10_000;
 
+/// Highest lock tier multiplier (3x), keeping a boosted position from draining the farm's emission
+pub static MAX_LOCK_MULTIPLIER_BPS: u64 = 30_000;
+
+/// Highest early unstake penalty (25%), so a penalty change can never take the whole position
+pub static MAX_EARLY_UNSTAKE_PENALTY_BPS: u64 = 2_500;
+
+/// Highest protocol fee (25%), on claimed rewards as on staked principal
+pub static MAX_PROTOCOL_FEE_BPS: u64 = 2_500;
+
 /// Cost to issue a new ESDT token (in eGLD)
 pub static TOKEN_ISSUANCE_COST: u64 = 50_000_000_000_000_000;
 
-pub static WAD_DECIMALS: usize = 18;
\ No newline at end of file
+/// Maximum number of reward tokens a farm can distribute, keeping claims bounded in gas
+pub static MAX_REWARD_TOKENS: usize = 10;
+
+pub static WAD_DECIMALS: usize = 18;
+
+/// Fixed point scale used by the reward accumulator (10^WAD_DECIMALS)
+pub static WAD: u64 = 1_000_000_000_000_000_000;
+
+/// Storage key of the staked token in the single-farm layout, set once its farm was created
+pub static LEGACY_STAKED_TOKEN_KEY: &[u8] = b"staked_token";
\ No newline at end of file
@@ -2,29 +2,67 @@
 
 pub static ERROR_PAUSED: &[u8] = b"Paused";
 pub static ERROR_NOT_PAUSED: &[u8] = b"Not paused";
+pub static ERROR_EMERGENCY_MODE: &[u8] = b"Emergency mode";
+pub static ERROR_NOT_EMERGENCY_MODE: &[u8] = b"Not in emergency mode";
 
+pub static ERROR_NOT_OWNER: &[u8] = b"Endpoint can only be called by owner";
+pub static ERROR_NOT_PENDING_OWNER: &[u8] = b"Only pending owner allowed";
+pub static ERROR_NO_PENDING_OWNER: &[u8] = b"No pending owner";
+pub static ERROR_UPGRADE_RIGHTS_NOT_TRANSFERABLE: &[u8] =
+    b"Contract must own itself or be owned by the new owner on chain";
 pub static ERROR_NOT_ADMIN: &[u8] = b"Only admin allowed";
+pub static ERROR_MISSING_ROLE: &[u8] = b"Missing role";
+
+pub static ERROR_ACTION_NOT_FOUND: &[u8] = b"Action not found";
+pub static ERROR_ACTION_NOT_READY: &[u8] = b"Action not ready";
+pub static ERROR_ACTION_ALREADY_APPROVED: &[u8] = b"Action already approved";
+pub static ERROR_APPROVAL_QUORUM_NOT_REACHED: &[u8] = b"Approval quorum not reached";
+pub static ERROR_INVALID_APPROVAL_THRESHOLD: &[u8] = b"Invalid approval threshold";
 
 pub static ERROR_WRONG_TIMEFRAME: &[u8] = b"Wrong timeframe";
 pub static ERROR_WRONG_START_TS: &[u8] = b"Wrong start timestamp";
 pub static ERROR_WRONG_REWARD_VALUES: &[u8] = b"Wrong reward values";
-pub static ERROR_FARM_ALREADY_CREATED: &[u8] = b"Farm already created";
+pub static ERROR_FARM_NOT_FOUND: &[u8] = b"Farm not found";
 
 pub static ERROR_ALREADY_STARTED: &[u8] = b"Already started";
 pub static ERROR_ALREADY_ENDED: &[u8] = b"Already ended";
 
 pub static ERROR_INVALID_REWARD_TOKEN: &[u8] = b"Invalid reward token";
+pub static ERROR_REWARD_TOKEN_ALREADY_ADDED: &[u8] = b"Reward token already added";
+pub static ERROR_TOO_MANY_REWARD_TOKENS: &[u8] = b"Too many reward tokens";
 pub static ERROR_ZERO_AMOUNT: &[u8] = b"Zero amount";
+pub static ERROR_DESTINATION_NOT_WHITELISTED: &[u8] = b"Destination not whitelisted";
 pub static ERROR_INSUFFICIENT_RESERVE: &[u8] = b"Insufficient reserve";
+pub static ERROR_RESERVE_BELOW_OBLIGATIONS: &[u8] = b"Reserve below projected obligations";
 
 pub static ERROR_WRONG_ISSUANCE_AMOUNT: &[u8] = b"Wrong token issuance amount";
 
 pub static ERROR_NO_REWARDS_APPLICABLE: &[u8] = b"No rewards applicable";
 pub static ERROR_NOT_ENOUGH_REWARDS: &[u8] = b"Not enough rewards";
+pub static ERROR_NO_UNPAID_REWARDS: &[u8] = b"No unpaid rewards";
 
 pub static ERROR_INVALID_SHARE_TOKEN: &[u8] = b"Invalid share token";
 
 pub static ERROR_ONLY_ONE_STAKING_TRANSFER_ALLOWED: &[u8] = b"Only one staking transfer allowed";
-pub static ERROR_STAKED_TOKEN_MISSING: &str = "Staked token transfer missing";
+pub static ERROR_STAKED_TOKEN_MISSING: &[u8] = b"Staked token transfer missing";
+
+pub static ERROR_UNSTAKE_AMOUNT_EXCEEDS: &[u8] = b"Unstake amount exceeds stake";
+pub static ERROR_POSITION_LOCKED: &[u8] = b"Position is locked";
+
+pub static ERROR_INVALID_LOCK_PERIOD: &[u8] = b"Invalid lock period";
+pub static ERROR_WRONG_MULTIPLIER: &[u8] = b"Wrong multiplier";
+pub static ERROR_LOCK_TIER_NOT_FOUND: &[u8] = b"Lock tier not found";
+pub static ERROR_NO_EXPIRED_BOOST: &[u8] = b"No expired boost";
+
+pub static ERROR_WRONG_PENALTY: &[u8] = b"Wrong penalty";
+pub static ERROR_PENALTY_NOT_SET: &[u8] = b"Early unstake penalty not set";
+pub static ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN: &[u8] = b"Staked token is not a reward token";
+pub static ERROR_CANNOT_BURN_EGLD: &[u8] = b"Cannot burn EGLD";
+pub static ERROR_MISSING_BURN_ROLE: &[u8] = b"Missing local burn role";
+
+pub static ERROR_UNBONDING_NOT_FOUND: &[u8] = b"Unbonding not found";
+pub static ERROR_NOTHING_TO_WITHDRAW: &[u8] = b"Nothing to withdraw";
 
-pub static ERROR_UNSTAKE_AMOUNT_EXCEEDS: &[u8] = b"Unstake amount exceeds stake";
\ No newline at end of file
+pub static ERROR_WRONG_FEE: &[u8] = b"Wrong fee";
+pub static ERROR_LEGACY_STORAGE_LAYOUT: &[u8] =
+    b"Single-farm storage cannot be upgraded in place";
\ No newline at end of file
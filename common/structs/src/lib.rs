@@ -2,11 +2,29 @@
 
 use multiversx_sc::{derive_imports::*, imports::*};
 
+/// How `reward_per_sec` is distributed: per staked unit (`FixedRate`) or pro-rata across the staked supply (`Pool`).
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy, Eq, Debug, ManagedVecItem)]
+pub enum EmissionMode {
+    FixedRate,
+    Pool,
+}
+
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug, ManagedVecItem)]
 pub struct Farm<M: ManagedTypeApi> {
+    pub farm_id: FarmId,
     pub staked_token: EgldOrEsdtTokenIdentifier<M>,
     pub share_token: TokenIdentifier<M>,
+    pub emission_mode: EmissionMode,
+    pub total_staked: BigUint<M>,
+    pub total_share_supply: BigUint<M>,
+    pub rewards: ManagedVec<M, RewardInfo<M>>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug, ManagedVecItem)]
+pub struct RewardInfo<M: ManagedTypeApi> {
     pub reward_token: EgldOrEsdtTokenIdentifier<M>,
     pub rewards_reserve: BigUint<M>,
     pub start_ts_ms: u64,
@@ -15,6 +33,128 @@ pub struct Farm<M: ManagedTypeApi> {
     pub reward_per_share: BigUint<M>,
 }
 
+/// Rates applied from `start_ts_ms` until the next checkpoint, along with the rewards accumulated
+/// per share unit (scaled by `WAD`) up to `start_ts_ms`.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug, ManagedVecItem)]
+pub struct RateCheckpoint<M: ManagedTypeApi> {
+    pub start_ts_ms: u64,
+    pub reward_per_sec: BigUint<M>,
+    pub reward_per_share: BigUint<M>,
+    pub acc_reward_per_share: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug, ManagedVecItem)]
+pub struct LockTier {
+    pub lock_period_ms: u64,
+    pub multiplier_bps: u64,
+}
+
+/// Lock of a boosted `Pool` share token nonce, kept until the boost is settled.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug)]
+pub struct ShareTokenBoost {
+    pub lock_until_ts_ms: u64,
+    pub multiplier_bps: u64,
+}
+
+/// Permissions the owner grants explicitly; being an admin does not imply any of them.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy, Eq, Debug)]
+pub enum Role {
+    Pauser,
+    RateManager,
+    Treasurer,
+    ScheduleManager,
+}
+
+/// What an early unstake is charged on: the unstaked principal or the rewards claimed with it.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy, Eq, Debug)]
+pub enum PenaltySource {
+    Principal,
+    Rewards,
+}
+
+/// Where early unstake penalties go: to an address, burned, or back into the farm's `rewards_reserve`.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug)]
+pub enum PenaltyDestination<M: ManagedTypeApi> {
+    Address(ManagedAddress<M>),
+    Burn,
+    Redistribute,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug)]
+pub struct EarlyUnstakePenalty<M: ManagedTypeApi> {
+    pub penalty_bps: u64,
+    pub source: PenaltySource,
+    pub destination: PenaltyDestination<M>,
+}
+
+/// Unstaked tokens waiting for the farm's unbonding period to pass before they can be withdrawn.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug, ManagedVecItem)]
+pub struct Unbonding<M: ManagedTypeApi> {
+    pub id: u64,
+    pub amount: BigUint<M>,
+    pub unlock_ts_ms: u64,
+}
+
+/// Where a reserve withdrawal goes: the treasury as it was when the withdrawal was requested, or
+/// a destination that must still be whitelisted when it is sent.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug)]
+pub enum WithdrawalDestination<M: ManagedTypeApi> {
+    Treasury(ManagedAddress<M>),
+    Whitelisted(ManagedAddress<M>),
+}
+
+/// A sensitive farm change that waits for the timelock delay before it can be executed.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug)]
+pub enum TimelockAction<M: ManagedTypeApi> {
+    ModifyEndTs {
+        farm_id: FarmId,
+        reward_token: EgldOrEsdtTokenIdentifier<M>,
+        new_end_ts_ms: u64,
+    },
+    ModifyRewards {
+        farm_id: FarmId,
+        reward_token: EgldOrEsdtTokenIdentifier<M>,
+        new_reward_per_sec: BigUint<M>,
+        new_reward_per_share: BigUint<M>,
+    },
+    WithdrawRewards {
+        farm_id: FarmId,
+        reward_token: EgldOrEsdtTokenIdentifier<M>,
+        amount: BigUint<M>,
+        to: WithdrawalDestination<M>,
+    },
+    SetEarlyUnstakePenalty {
+        farm_id: FarmId,
+        penalty: EarlyUnstakePenalty<M>,
+    },
+    SetTimelockDelay {
+        delay_ms: u64,
+    },
+    SetProtocolFees {
+        reward_fee_bps: u64,
+        stake_fee_bps: u64,
+    },
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug)]
+pub struct PendingAction<M: ManagedTypeApi> {
+    pub id: u64,
+    pub proposer: ManagedAddress<M>,
+    pub eta_ms: u64,
+    pub action: TimelockAction<M>,
+}
+
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug, ManagedVecItem)]
 pub struct ShareToken<M: ManagedTypeApi> {
@@ -22,14 +162,18 @@ pub struct ShareToken<M: ManagedTypeApi> {
     pub amount: BigUint<M>,
 }
 
+pub type FarmId = u64;
 pub type Nonce = u64;
 pub type Amount<M> = BigUint<M>;
 pub type ShareTokenType<M> = MultiValue2<Nonce, Amount<M>>;
 
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Eq, Debug, ManagedVecItem)]
-pub struct ShareTokenAttributes {
+pub struct ShareTokenAttributes<M: ManagedTypeApi> {
     pub update_ts_ms: u64,
+    pub acc_reward_per_share: ManagedVec<M, BigUint<M>>,
+    pub lock_until_ts_ms: u64,
+    pub multiplier_bps: u64,
 }
 
 #[type_abi]
@@ -37,7 +181,10 @@ pub struct ShareTokenAttributes {
 pub struct ShareTokenMergedData<M: ManagedTypeApi> {
     pub update_ts_ms: u64,
     pub token_supply: BigUint<M>,
-    pub reward_amount: BigUint<M>,
+    pub boosted_supply: BigUint<M>,
+    pub lock_until_ts_ms: u64,
+    pub multiplier_bps: u64,
+    pub rewards: ManagedVec<M, EgldOrEsdtTokenPayment<M>>,
 }
 
 #[type_abi]
@@ -45,6 +192,11 @@ pub struct ShareTokenMergedData<M: ManagedTypeApi> {
 pub struct ShareTokenMergedDataWithBurns<M: ManagedTypeApi> {
     pub update_ts_ms: u64,
     pub token_supply: BigUint<M>,
-    pub reward_amount: BigUint<M>,
+    pub boosted_supply: BigUint<M>,
+    pub lock_until_ts_ms: u64,
+    pub multiplier_bps: u64,
+    pub rewards: ManagedVec<M, EgldOrEsdtTokenPayment<M>>,
     pub token_burns: ManagedVec<M, ShareToken<M>>,
+    /// Share token nonce updated in place instead of being burned, `0` if all of them are burned.
+    pub kept_nonce: u64,
 }
\ No newline at end of file
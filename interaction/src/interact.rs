@@ -0,0 +1,497 @@
+mod interact_cli;
+mod interact_config;
+mod interact_state;
+
+#[path = "../../output/tokenstaking_proxy.rs"]
+pub mod tokenstaking_proxy;
+
+use clap::Parser;
+use constants::TOKEN_ISSUANCE_COST;
+use multiversx_sc_snippets::imports::*;
+use structs::{EmissionMode, Farm, Role};
+use tokenstaking_proxy::TemplateProxy;
+
+pub use interact_cli::*;
+pub use interact_config::{Config, Network};
+pub use interact_state::Deployments;
+
+const CODE_PATH: &str = "mxsc:output/tokenstaking.mxsc.json";
+
+const DEPLOY_GAS: u64 = 100_000_000;
+const CREATE_FARM_GAS: u64 = 100_000_000;
+const ENDPOINT_GAS: u64 = 30_000_000;
+
+pub async fn tokenstaking_cli() {
+    let cli = InteractCli::parse();
+    let mut interact = ContractInteract::new(Config::load(cli.network)).await;
+    match cli.command {
+        InteractCliCommand::Deploy => interact.deploy().await,
+        InteractCliCommand::Upgrade => interact.upgrade().await,
+        InteractCliCommand::CreateFarm(args) => interact.create_farm(&args).await,
+        InteractCliCommand::AddAdmin(args) => interact.add_admin(&args.address).await,
+        InteractCliCommand::RemoveAdmin(args) => interact.remove_admin(&args.address).await,
+        InteractCliCommand::GrantRole(args) => {
+            interact.grant_role(args.role.into(), &args.address).await
+        }
+        InteractCliCommand::RevokeRole(args) => {
+            interact.revoke_role(args.role.into(), &args.address).await
+        }
+        InteractCliCommand::DepositRewards(args) => {
+            interact
+                .deposit_rewards(args.farm_id, &args.token, args.amount)
+                .await
+        }
+        InteractCliCommand::WithdrawRewards(args) => {
+            interact
+                .withdraw_rewards(
+                    args.farm_id,
+                    args.amount,
+                    args.token.as_deref(),
+                    args.to.as_deref(),
+                )
+                .await
+        }
+        InteractCliCommand::Pause => interact.pause().await,
+        InteractCliCommand::Unpause => interact.unpause().await,
+        InteractCliCommand::Stake(args) => {
+            interact
+                .stake(args.farm_id, args.amount, args.lock_period_ms)
+                .await
+        }
+        InteractCliCommand::Unstake(args) => {
+            interact
+                .unstake(args.farm_id, args.nonce, args.amount, args.unstake_amount)
+                .await
+        }
+        InteractCliCommand::Claim(args) => {
+            interact
+                .claim_rewards(args.farm_id, args.nonce, args.amount)
+                .await
+        }
+        InteractCliCommand::FarmInfo(args) => {
+            println!("{:#?}", interact.farm_info(args.farm_id).await)
+        }
+        InteractCliCommand::Farms(args) => {
+            println!("{:#?}", interact.farms(args.from, args.size).await)
+        }
+        InteractCliCommand::ClaimableRewards(args) => {
+            for (token, amount) in interact
+                .claimable_rewards(args.farm_id, &args.address, args.nonce, args.amount)
+                .await
+            {
+                println!("{token}: {amount}");
+            }
+        }
+        InteractCliCommand::IsPaused => println!("{}", interact.is_paused().await),
+        InteractCliCommand::UserStaked(args) => {
+            println!(
+                "{}",
+                interact.user_staked(args.farm_id, &args.address).await
+            )
+        }
+    }
+}
+
+pub struct ContractInteract {
+    interactor: Interactor,
+    wallet_address: Address,
+    contract_code: BytesValue,
+    config: Config,
+    deployments: Deployments,
+}
+
+impl ContractInteract {
+    pub async fn new(config: Config) -> Self {
+        let mut interactor = Interactor::new(&config.gateway_uri)
+            .await
+            .use_chain_simulator(config.use_chain_simulator());
+
+        interactor.set_current_dir_from_workspace("interaction");
+        let wallet_address = interactor.register_wallet(config.wallet()).await;
+
+        // the chain simulator starts before the system smart contracts are enabled
+        if config.use_chain_simulator() {
+            interactor.generate_blocks_until_epoch(1).await.unwrap();
+        }
+
+        let contract_code = BytesValue::interpret_from(
+            CODE_PATH,
+            &InterpreterContext::default().with_dir(interact_config::repository_root()),
+        );
+
+        ContractInteract {
+            interactor,
+            wallet_address,
+            contract_code,
+            deployments: Deployments::load(config.network),
+            config,
+        }
+    }
+
+    pub fn wallet_address(&self) -> &Address {
+        &self.wallet_address
+    }
+
+    /// `SC_ADDRESS` from the env file, falling back to the address saved in `deployments.json`.
+    pub fn contract_address(&self) -> Bech32Address {
+        let address = self
+            .config
+            .sc_address
+            .clone()
+            .or_else(|| self.deployments.address())
+            .expect("no contract address, deploy first or set SC_ADDRESS");
+
+        Bech32Address::from_bech32_string(address)
+    }
+
+    // === Owner ===
+
+    pub async fn deploy(&mut self) {
+        let new_address = self
+            .interactor
+            .tx()
+            .from(&self.wallet_address)
+            .gas(DEPLOY_GAS)
+            .typed(TemplateProxy)
+            .init()
+            .code(&self.contract_code)
+            .code_metadata(CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE)
+            .returns(ReturnsNewBech32Address)
+            .run()
+            .await;
+
+        self.deployments
+            .set_address(&new_address.to_bech32_string());
+        println!("new address: {new_address}");
+    }
+
+    pub async fn upgrade(&mut self) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(DEPLOY_GAS)
+            .typed(TemplateProxy)
+            .upgrade()
+            .code(&self.contract_code)
+            .code_metadata(CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE)
+            .run()
+            .await;
+
+        println!("upgraded {}", self.contract_address());
+    }
+
+    pub async fn add_admin(&mut self, address: &str) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .add_admin(MultiValueVec::from(vec![
+                Bech32Address::from_bech32_string(address.to_string()),
+            ]))
+            .run()
+            .await;
+    }
+
+    pub async fn remove_admin(&mut self, address: &str) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .remove_admin(MultiValueVec::from(vec![
+                Bech32Address::from_bech32_string(address.to_string()),
+            ]))
+            .run()
+            .await;
+    }
+
+    pub async fn grant_role(&mut self, role: Role, address: &str) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .grant_role(
+                role,
+                MultiValueVec::from(vec![Bech32Address::from_bech32_string(address.to_string())]),
+            )
+            .run()
+            .await;
+    }
+
+    pub async fn revoke_role(&mut self, role: Role, address: &str) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .revoke_role(
+                role,
+                MultiValueVec::from(vec![Bech32Address::from_bech32_string(address.to_string())]),
+            )
+            .run()
+            .await;
+    }
+
+    // === Admin ===
+
+    pub async fn create_farm(&mut self, args: &CreateFarmArgs) {
+        let emission_mode = if args.pool {
+            EmissionMode::Pool
+        } else {
+            EmissionMode::FixedRate
+        };
+
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(CREATE_FARM_GAS)
+            .typed(TemplateProxy)
+            .create_farm(
+                token_identifier(&args.staked_token),
+                token_identifier(&args.reward_token),
+                args.start_ts_ms,
+                args.end_ts_ms,
+                BigUint::from(args.reward_per_sec),
+                BigUint::from(args.reward_per_share),
+                ManagedBuffer::from(args.share_token_name.as_str()),
+                ManagedBuffer::from(args.share_token_ticker.as_str()),
+                OptionalValue::Some(emission_mode),
+            )
+            .egld(TOKEN_ISSUANCE_COST)
+            .run()
+            .await;
+    }
+
+    pub async fn deposit_rewards(&mut self, farm_id: u64, token: &str, amount: u128) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .deposit_rewards(farm_id)
+            .egld_or_single_esdt(&token_identifier(token), 0, &BigUint::from(amount))
+            .run()
+            .await;
+    }
+
+    pub async fn withdraw_rewards(
+        &mut self,
+        farm_id: u64,
+        amount: u128,
+        token: Option<&str>,
+        to: Option<&str>,
+    ) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .withdraw_rewards(
+                farm_id,
+                BigUint::from(amount),
+                OptionalValue::from(token.map(token_identifier)),
+                OptionalValue::from(
+                    to.map(|address| Bech32Address::from_bech32_string(address.to_string())),
+                ),
+            )
+            .run()
+            .await;
+    }
+
+    pub async fn pause(&mut self) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .pause()
+            .run()
+            .await;
+    }
+
+    pub async fn unpause(&mut self) {
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .unpause()
+            .run()
+            .await;
+    }
+
+    // === Users ===
+
+    pub async fn stake(&mut self, farm_id: u64, amount: u128, lock_period_ms: Option<u64>) {
+        let farm = self.farm_info(farm_id).await;
+
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .stake(farm_id, OptionalValue::from(lock_period_ms))
+            .egld_or_single_esdt(&farm.staked_token, 0, &BigUint::from(amount))
+            .run()
+            .await;
+    }
+
+    pub async fn unstake(
+        &mut self,
+        farm_id: u64,
+        nonce: u64,
+        amount: u128,
+        unstake_amount: Option<u128>,
+    ) {
+        let farm = self.farm_info(farm_id).await;
+
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .unstake(OptionalValue::from(unstake_amount.map(BigUint::from)))
+            .single_esdt(&farm.share_token, nonce, &BigUint::from(amount))
+            .run()
+            .await;
+    }
+
+    pub async fn claim_rewards(&mut self, farm_id: u64, nonce: u64, amount: u128) {
+        let farm = self.farm_info(farm_id).await;
+
+        let contract_address = self.contract_address();
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(contract_address)
+            .gas(ENDPOINT_GAS)
+            .typed(TemplateProxy)
+            .claim_rewards_endpoint(OptionalValue::<bool>::None)
+            .single_esdt(&farm.share_token, nonce, &BigUint::from(amount))
+            .run()
+            .await;
+    }
+
+    // === Views ===
+
+    pub async fn farm_info(&mut self, farm_id: u64) -> Farm<StaticApi> {
+        let contract_address = self.contract_address();
+        self.interactor
+            .query()
+            .to(contract_address)
+            .typed(TemplateProxy)
+            .get_farm_info(farm_id)
+            .returns(ReturnsResult)
+            .run()
+            .await
+    }
+
+    pub async fn farms(&mut self, from: usize, size: usize) -> Vec<Farm<StaticApi>> {
+        let contract_address = self.contract_address();
+        self.interactor
+            .query()
+            .to(contract_address)
+            .typed(TemplateProxy)
+            .get_farms(from, size)
+            .returns(ReturnsResult)
+            .run()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    pub async fn claimable_rewards(
+        &mut self,
+        farm_id: u64,
+        address: &str,
+        nonce: u64,
+        amount: u128,
+    ) -> Vec<(String, RustBigUint)> {
+        let contract_address = self.contract_address();
+        self.interactor
+            .query()
+            .to(contract_address)
+            .typed(TemplateProxy)
+            .get_claimable_rewards(
+                farm_id,
+                Bech32Address::from_bech32_string(address.to_string()),
+                MultiValueVec::from(vec![MultiValue2::from((
+                    nonce,
+                    BigUint::<StaticApi>::from(amount),
+                ))]),
+            )
+            .returns(ReturnsResult)
+            .run()
+            .await
+            .into_iter()
+            .map(|reward| {
+                (
+                    reward.token_identifier.into_name().to_string(),
+                    RustBigUint::from_bytes_be(reward.amount.to_bytes_be().as_slice()),
+                )
+            })
+            .collect()
+    }
+
+    pub async fn is_paused(&mut self) -> bool {
+        let contract_address = self.contract_address();
+        self.interactor
+            .query()
+            .to(contract_address)
+            .typed(TemplateProxy)
+            .is_paused()
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn user_staked(&mut self, farm_id: u64, address: &str) -> RustBigUint {
+        let contract_address = self.contract_address();
+        self.interactor
+            .query()
+            .to(contract_address)
+            .typed(TemplateProxy)
+            .user_staked(
+                farm_id,
+                Bech32Address::from_bech32_string(address.to_string()),
+            )
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+}
+
+/// Parses a token identifier, `EGLD` standing for eGLD.
+fn token_identifier(token: &str) -> EgldOrEsdtTokenIdentifier<StaticApi> {
+    if token == "EGLD" {
+        EgldOrEsdtTokenIdentifier::egld()
+    } else {
+        EgldOrEsdtTokenIdentifier::esdt(TokenIdentifier::from(token))
+    }
+}
@@ -0,0 +1,172 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use structs::Role;
+
+use crate::interact_config::Network;
+
+/// Token staking interactor
+#[derive(Clone, Debug, Parser)]
+#[command(version, about)]
+pub struct InteractCli {
+    /// Network to interact with, reading its settings from `.env.<network>`
+    #[arg(short, long, value_enum, default_value_t = Network::Devnet)]
+    pub network: Network,
+
+    #[command(subcommand)]
+    pub command: InteractCliCommand,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum InteractCliCommand {
+    /// Deploy the contract and save its address to `deployments.json`
+    Deploy,
+    /// Upgrade the deployed contract
+    Upgrade,
+    /// Create a farm, issuing its share token
+    CreateFarm(CreateFarmArgs),
+    AddAdmin(AddressArgs),
+    RemoveAdmin(AddressArgs),
+    GrantRole(RoleArgs),
+    RevokeRole(RoleArgs),
+    DepositRewards(DepositRewardsArgs),
+    WithdrawRewards(WithdrawRewardsArgs),
+    Pause,
+    Unpause,
+    Stake(StakeArgs),
+    Unstake(UnstakeArgs),
+    Claim(ShareTokenArgs),
+    /// Print the farm parameters and totals
+    FarmInfo(FarmArgs),
+    /// Print a page of farms
+    Farms(FarmsArgs),
+    /// Print the rewards claimable by a share token
+    ClaimableRewards(ClaimableRewardsArgs),
+    IsPaused,
+    UserStaked(UserStakedArgs),
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct CreateFarmArgs {
+    #[arg(long)]
+    pub staked_token: String,
+    #[arg(long)]
+    pub reward_token: String,
+    #[arg(long)]
+    pub start_ts_ms: u64,
+    #[arg(long)]
+    pub end_ts_ms: u64,
+    #[arg(long)]
+    pub reward_per_sec: u128,
+    #[arg(long)]
+    pub reward_per_share: u128,
+    #[arg(long)]
+    pub share_token_name: String,
+    #[arg(long)]
+    pub share_token_ticker: String,
+    /// Distribute `reward_per_sec` pro-rata across the staked supply instead of per staked unit
+    #[arg(long, default_value_t = false)]
+    pub pool: bool,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct AddressArgs {
+    pub address: String,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct RoleArgs {
+    #[arg(value_enum)]
+    pub role: RoleArg,
+    pub address: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RoleArg {
+    Pauser,
+    RateManager,
+    Treasurer,
+    ScheduleManager,
+}
+
+impl From<RoleArg> for Role {
+    fn from(role: RoleArg) -> Self {
+        match role {
+            RoleArg::Pauser => Role::Pauser,
+            RoleArg::RateManager => Role::RateManager,
+            RoleArg::Treasurer => Role::Treasurer,
+            RoleArg::ScheduleManager => Role::ScheduleManager,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct FarmArgs {
+    pub farm_id: u64,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct FarmsArgs {
+    #[arg(long, default_value_t = 0)]
+    pub from: usize,
+    #[arg(long, default_value_t = 10)]
+    pub size: usize,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct DepositRewardsArgs {
+    pub farm_id: u64,
+    /// Reward token identifier, `EGLD` for eGLD
+    pub token: String,
+    pub amount: u128,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct WithdrawRewardsArgs {
+    pub farm_id: u64,
+    pub amount: u128,
+    /// Reward token to withdraw, the farm's primary reward token if omitted
+    #[arg(long)]
+    pub token: Option<String>,
+    /// Whitelisted destination, the treasury if omitted
+    #[arg(long, requires = "token")]
+    pub to: Option<String>,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct StakeArgs {
+    pub farm_id: u64,
+    pub amount: u128,
+    /// Lock the stake for one of the farm's lock tiers
+    #[arg(long)]
+    pub lock_period_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ShareTokenArgs {
+    pub farm_id: u64,
+    pub nonce: u64,
+    pub amount: u128,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct UnstakeArgs {
+    pub farm_id: u64,
+    pub nonce: u64,
+    pub amount: u128,
+    /// Part of the share token to unstake, all of it if omitted
+    #[arg(long)]
+    pub unstake_amount: Option<u128>,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ClaimableRewardsArgs {
+    pub farm_id: u64,
+    pub address: String,
+    pub nonce: u64,
+    pub amount: u128,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct UserStakedArgs {
+    pub farm_id: u64,
+    pub address: String,
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use multiversx_sc_snippets::imports::*;
+
+const DEVNET_GATEWAY: &str = "https://devnet-gateway.multiversx.com";
+const MAINNET_GATEWAY: &str = "https://gateway.multiversx.com";
+const CHAIN_SIMULATOR_GATEWAY: &str = "http://localhost:8085";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Network {
+    Devnet,
+    Mainnet,
+    Simulator,
+}
+
+impl Network {
+    /// Key of the network in `deployments.json` and suffix of its `.env` file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Devnet => "devnet",
+            Network::Mainnet => "mainnet",
+            Network::Simulator => "simulator",
+        }
+    }
+}
+
+/// Interactor settings, read from the `.env.<network>` file at the root of the repository.
+///
+/// The file holds `PEM`, the wallet used to sign transactions, and optionally `SC_ADDRESS`,
+/// which takes precedence over the address saved in `deployments.json`.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub network: Network,
+    pub gateway_uri: String,
+    pub pem: Option<String>,
+    pub sc_address: Option<String>,
+}
+
+impl Config {
+    pub fn load(network: Network) -> Self {
+        let env = read_env_file(&repository_root().join(format!(".env.{}", network.name())));
+
+        let gateway_uri = env
+            .get("GATEWAY")
+            .cloned()
+            .unwrap_or_else(|| default_gateway(network).to_string());
+        let pem = env.get("PEM").cloned();
+        if network != Network::Simulator {
+            assert!(
+                pem.is_some(),
+                "PEM missing from .env.{}, see .env.example",
+                network.name()
+            );
+        }
+
+        Config {
+            network,
+            gateway_uri,
+            pem,
+            sc_address: env
+                .get("SC_ADDRESS")
+                .cloned()
+                .filter(|address| is_bech32_address(address)),
+        }
+    }
+
+    /// Local chain simulator, signing with the simulator's prefunded test wallet.
+    pub fn chain_simulator_config() -> Self {
+        Config {
+            network: Network::Simulator,
+            gateway_uri: CHAIN_SIMULATOR_GATEWAY.to_string(),
+            pem: None,
+            sc_address: None,
+        }
+    }
+
+    pub fn use_chain_simulator(&self) -> bool {
+        self.network == Network::Simulator
+    }
+
+    pub fn wallet(&self) -> Wallet {
+        match &self.pem {
+            Some(pem) => Wallet::from_pem_file(pem).expect("failed to load the PEM wallet"),
+            None => test_wallets::alice(),
+        }
+    }
+}
+
+fn default_gateway(network: Network) -> &'static str {
+    match network {
+        Network::Devnet => DEVNET_GATEWAY,
+        Network::Mainnet => MAINNET_GATEWAY,
+        Network::Simulator => CHAIN_SIMULATOR_GATEWAY,
+    }
+}
+
+/// Root of the repository, resolved at build time so that the interactor finds its files from
+/// any working directory.
+pub fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the interactor lives inside the repository")
+        .to_path_buf()
+}
+
+/// Placeholder values such as the one in `.env.example` are ignored.
+fn is_bech32_address(address: &str) -> bool {
+    address.starts_with("erd1")
+}
+
+fn read_env_file(path: &Path) -> HashMap<String, String> {
+    if !path.exists() {
+        return HashMap::new();
+    }
+
+    fs::read_to_string(path)
+        .expect("failed to read the env file")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_string(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect()
+}
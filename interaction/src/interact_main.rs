@@ -0,0 +1,6 @@
+use tokenstaking_interact::tokenstaking_cli;
+
+#[tokio::main]
+async fn main() {
+    tokenstaking_cli().await;
+}
@@ -0,0 +1,61 @@
+use std::{fs, path::PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::interact_config::{repository_root, Network};
+
+const DEPLOYMENTS_FILE: &str = "deployments.json";
+const CONTRACT_LABEL: &str = "SC";
+
+/// Contract addresses per network, kept in `deployments.json` at the root of the repository.
+///
+/// Chain simulator deployments are short-lived and only kept in memory.
+pub struct Deployments {
+    network: Network,
+    contents: Value,
+}
+
+impl Deployments {
+    pub fn load(network: Network) -> Self {
+        let contents = fs::read_to_string(deployments_path())
+            .ok()
+            .and_then(|file| serde_json::from_str(&file).ok())
+            .unwrap_or_else(|| json!({}));
+
+        Deployments { network, contents }
+    }
+
+    pub fn address(&self) -> Option<String> {
+        self.contents[self.network.name()]
+            .as_array()?
+            .iter()
+            .find(|entry| entry["label"] == CONTRACT_LABEL)
+            .and_then(|entry| entry["address"].as_str())
+            .filter(|address| !address.is_empty())
+            .map(str::to_string)
+    }
+
+    pub fn set_address(&mut self, address: &str) {
+        let entries = self.contents[self.network.name()]
+            .as_array_mut()
+            .map(std::mem::take)
+            .unwrap_or_default();
+        let mut entries: Vec<Value> = entries
+            .into_iter()
+            .filter(|entry| entry["label"] != CONTRACT_LABEL)
+            .collect();
+        entries.insert(0, json!({ "address": address, "label": CONTRACT_LABEL }));
+        self.contents[self.network.name()] = Value::Array(entries);
+
+        if self.network == Network::Simulator {
+            return;
+        }
+
+        let file = serde_json::to_string_pretty(&self.contents).unwrap();
+        fs::write(deployments_path(), file).expect("failed to write deployments.json");
+    }
+}
+
+fn deployments_path() -> PathBuf {
+    repository_root().join(DEPLOYMENTS_FILE)
+}
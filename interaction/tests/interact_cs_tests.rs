@@ -0,0 +1,51 @@
+use multiversx_sc_snippets::imports::Bech32Address;
+use structs::Role;
+use tokenstaking_interact::{Config, ContractInteract, CreateFarmArgs};
+
+// Runs against a local chain simulator: `sc-meta cs start`, then
+// `cargo test --features chain-simulator-tests`.
+
+#[tokio::test]
+#[cfg_attr(not(feature = "chain-simulator-tests"), ignore)]
+async fn deploy_and_pause_cs_test() {
+    let mut interact = ContractInteract::new(Config::chain_simulator_config()).await;
+
+    interact.deploy().await;
+    assert!(!interact.is_paused().await);
+
+    let owner = Bech32Address::from(interact.wallet_address()).to_bech32_string();
+    interact.grant_role(Role::Pauser, &owner).await;
+
+    interact.pause().await;
+    assert!(interact.is_paused().await);
+
+    interact.unpause().await;
+    assert!(!interact.is_paused().await);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "chain-simulator-tests"), ignore)]
+async fn create_farm_cs_test() {
+    let mut interact = ContractInteract::new(Config::chain_simulator_config()).await;
+
+    interact.deploy().await;
+    let owner = Bech32Address::from(interact.wallet_address()).to_bech32_string();
+    interact.add_admin(&owner).await;
+
+    interact
+        .create_farm(&CreateFarmArgs {
+            staked_token: "EGLD".to_string(),
+            reward_token: "EGLD".to_string(),
+            start_ts_ms: u64::MAX / 2,
+            end_ts_ms: u64::MAX / 2 + 1_000_000,
+            reward_per_sec: 1,
+            reward_per_share: 1,
+            share_token_name: "StakedEgld".to_string(),
+            share_token_ticker: "SEGLD".to_string(),
+            pool: false,
+        })
+        .await;
+
+    let farms = interact.farms(0, 10).await;
+    assert_eq!(farms.len(), 1);
+}
@@ -62,6 +62,12 @@ where
     To: TxTo<Env>,
     Gas: TxGas<Env>,
 {
+    /// Records the owner of deployments that predate it, so that the contract can later be 
+    /// made its own owner on chain without losing track of who owns it. 
+    ///  
+    /// Deployments of the single-farm layout cannot be upgraded in place: their farm state is 
+    /// stored under keys no farm id points to, and the staked supply it never tracked cannot be 
+    /// rebuilt on chain. Such deployments are rejected and must be migrated to a new deployment. 
     pub fn upgrade(
         self,
     ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
@@ -81,6 +87,38 @@ where
     To: TxTo<Env>,
     Gas: TxGas<Env>,
 {
+    /// Makes `new_owner` the pending owner, replacing any previous proposal. 
+    pub fn propose_owner<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        new_owner: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("proposeOwner")
+            .argument(&new_owner)
+            .original_result()
+    }
+
+    pub fn cancel_ownership_transfer(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelOwnershipTransfer")
+            .original_result()
+    }
+
+    pub fn accept_ownership(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("acceptOwnership")
+            .original_result()
+    }
+
     pub fn add_admin<
         Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
     >(
@@ -107,6 +145,84 @@ where
             .original_result()
     }
 
+    pub fn grant_role<
+        Arg0: ProxyArg<structs::Role>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        role: Arg0,
+        addresses: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantRole")
+            .argument(&role)
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn revoke_role<
+        Arg0: ProxyArg<structs::Role>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        role: Arg0,
+        addresses: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeRole")
+            .argument(&role)
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn renounce_admin(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("renounceAdmin")
+            .original_result()
+    }
+
+    pub fn renounce_role<
+        Arg0: ProxyArg<structs::Role>,
+    >(
+        self,
+        role: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("renounceRole")
+            .argument(&role)
+            .original_result()
+    }
+
+    /// Number of admin approvals a timelock action needs before it can be executed; `0` or `1` 
+    /// lets the proposer's role alone authorize it. Admins can't then be removed below it. 
+    pub fn set_approval_threshold<
+        Arg0: ProxyArg<usize>,
+    >(
+        self,
+        threshold: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setApprovalThreshold")
+            .argument(&threshold)
+            .original_result()
+    }
+
+    pub fn get_owner(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOwner")
+            .original_result()
+    }
+
     pub fn is_admin<
         Arg0: ProxyArg<ManagedAddress<Env::Api>>,
     >(
@@ -120,6 +236,31 @@ where
             .original_result()
     }
 
+    pub fn has_role<
+        Arg0: ProxyArg<structs::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("hasRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_pending_owner(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingOwner")
+            .original_result()
+    }
+
     pub fn admins(
         self,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
@@ -129,6 +270,41 @@ where
             .original_result()
     }
 
+    pub fn role_members<
+        Arg0: ProxyArg<structs::Role>,
+    >(
+        self,
+        role: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRoleMembers")
+            .argument(&role)
+            .original_result()
+    }
+
+    pub fn approval_threshold(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getApprovalThreshold")
+            .original_result()
+    }
+
+    pub fn action_approvers<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        action_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getActionApprovers")
+            .argument(&action_id)
+            .original_result()
+    }
+
     pub fn pause(
         self,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
@@ -147,6 +323,25 @@ where
             .original_result()
     }
 
+    /// Lets users exit through `emergencyUnstake` while the contract is paused. 
+    pub fn enable_emergency_mode(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("enableEmergencyMode")
+            .original_result()
+    }
+
+    pub fn disable_emergency_mode(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("disableEmergencyMode")
+            .original_result()
+    }
+
     pub fn is_paused(
         self,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
@@ -156,6 +351,61 @@ where
             .original_result()
     }
 
+    pub fn emergency_mode(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isEmergencyMode")
+            .original_result()
+    }
+
+    /// Delay applied to the actions queued from now on; `0` applies them right away. A shorter 
+    /// delay is itself queued behind the current one. 
+    pub fn set_timelock_delay<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        delay_ms: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTimelockDelay")
+            .argument(&delay_ms)
+            .original_result()
+    }
+
+    pub fn cancel_action<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        action_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelAction")
+            .argument(&action_id)
+            .original_result()
+    }
+
+    pub fn get_pending_actions(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, structs::PendingAction<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingActions")
+            .original_result()
+    }
+
+    pub fn timelock_delay_ms(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTimelockDelay")
+            .original_result()
+    }
+
     pub fn create_farm<
         Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
         Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
@@ -165,6 +415,7 @@ where
         Arg5: ProxyArg<BigUint<Env::Api>>,
         Arg6: ProxyArg<ManagedBuffer<Env::Api>>,
         Arg7: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg8: ProxyArg<OptionalValue<structs::EmissionMode>>,
     >(
         self,
         staked_token: Arg0,
@@ -175,6 +426,7 @@ where
         reward_per_share: Arg5,
         share_token_display_name: Arg6,
         share_token_ticker: Arg7,
+        opt_emission_mode: Arg8,
     ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
         self.wrapped_tx
             .raw_call("createFarm")
@@ -186,122 +438,965 @@ where
             .argument(&reward_per_share)
             .argument(&share_token_display_name)
             .argument(&share_token_ticker)
+            .argument(&opt_emission_mode)
+            .original_result()
+    }
+
+    pub fn add_reward_token<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<BigUint<Env::Api>>,
+        Arg5: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        reward_token: Arg1,
+        start_ts_ms: Arg2,
+        end_ts_ms: Arg3,
+        reward_per_sec: Arg4,
+        reward_per_share: Arg5,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addRewardToken")
+            .argument(&farm_id)
+            .argument(&reward_token)
+            .argument(&start_ts_ms)
+            .argument(&end_ts_ms)
+            .argument(&reward_per_sec)
+            .argument(&reward_per_share)
             .original_result()
     }
 
     pub fn modify_start_ts<
         Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
     >(
         self,
-        new_start_ts_ms: Arg0,
+        farm_id: Arg0,
+        new_start_ts_ms: Arg1,
+        opt_reward_token: Arg2,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
             .raw_call("modifyStartTs")
+            .argument(&farm_id)
             .argument(&new_start_ts_ms)
+            .argument(&opt_reward_token)
             .original_result()
     }
 
+    /// Queued as a timelock action while a timelock delay is set. 
     pub fn modify_end_ts<
         Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
     >(
         self,
-        new_end_ts_ms: Arg0,
+        farm_id: Arg0,
+        new_end_ts_ms: Arg1,
+        opt_reward_token: Arg2,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
             .raw_call("modifyEndTs")
+            .argument(&farm_id)
             .argument(&new_end_ts_ms)
+            .argument(&opt_reward_token)
             .original_result()
     }
 
+    /// Queued as a timelock action while a timelock delay is set. 
     pub fn modify_rewards<
-        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg0: ProxyArg<u64>,
         Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
     >(
         self,
-        new_reward_per_sec: Arg0,
-        new_reward_per_share: Arg1,
+        farm_id: Arg0,
+        new_reward_per_sec: Arg1,
+        new_reward_per_share: Arg2,
+        opt_reward_token: Arg3,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
             .raw_call("modifyRewards")
+            .argument(&farm_id)
             .argument(&new_reward_per_sec)
             .argument(&new_reward_per_share)
+            .argument(&opt_reward_token)
             .original_result()
     }
 
-    pub fn deposit_rewards(
+    pub fn deposit_rewards<
+        Arg0: ProxyArg<u64>,
+    >(
         self,
+        farm_id: Arg0,
     ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
         self.wrapped_tx
             .raw_call("depositRewards")
+            .argument(&farm_id)
             .original_result()
     }
 
+    /// Sends `amount` of the reserve to the treasury, or to `opt_to` if it is a whitelisted 
+    /// destination, which also requires the reward token to be given; queued as a timelock 
+    /// action while a timelock delay is set, keeping the treasury of the time it was queued and 
+    /// checking a whitelisted destination again on execution. 
     pub fn withdraw_rewards<
-        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+        Arg3: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
     >(
         self,
-        amount: Arg0,
+        farm_id: Arg0,
+        amount: Arg1,
+        opt_reward_token: Arg2,
+        opt_to: Arg3,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
             .raw_call("withdrawRewards")
+            .argument(&farm_id)
             .argument(&amount)
+            .argument(&opt_reward_token)
+            .argument(&opt_to)
             .original_result()
     }
 
-    pub fn get_farm_info(
+    /// Applies a pending timelock action once its delay has elapsed and its approvals reached the 
+    /// threshold; anyone can call it. 
+    pub fn execute_action<
+        Arg0: ProxyArg<u64>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, structs::Farm<Env::Api>> {
+        action_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getFarmInfo")
+            .raw_call("executeAction")
+            .argument(&action_id)
             .original_result()
     }
 
-    pub fn stake(
+    /// Approves a pending timelock action, which is then applied through `executeAction`. 
+    pub fn approve_action<
+        Arg0: ProxyArg<u64>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        action_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
-            .raw_call("stake")
+            .payment(NotPayable)
+            .raw_call("approveAction")
+            .argument(&action_id)
             .original_result()
     }
 
-    pub fn unstake<
-        Arg0: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+    pub fn set_treasury<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
     >(
         self,
-        opt_unstake_amount: Arg0,
-    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        treasury: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
-            .raw_call("unstake")
-            .argument(&opt_unstake_amount)
+            .payment(NotPayable)
+            .raw_call("setTreasury")
+            .argument(&treasury)
             .original_result()
     }
 
-    pub fn claim_rewards_endpoint(
+    pub fn add_withdrawal_destinations<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
-            .raw_call("claimRewards")
+            .payment(NotPayable)
+            .raw_call("addWithdrawalDestinations")
+            .argument(&addresses)
             .original_result()
     }
 
-    pub fn get_claimable_rewards<
-        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
-        Arg1: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<u64, BigUint<Env::Api>>>>,
+    pub fn remove_withdrawal_destinations<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
     >(
         self,
-        address: Arg0,
-        share_tokens: Arg1,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getClaimableRewards")
-            .argument(&address)
-            .argument(&share_tokens)
+            .raw_call("removeWithdrawalDestinations")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    /// When enabled, `modifyRewards`, `modifyEndTs` and `withdrawRewards` are refused if they 
+    /// leave a reserve below the rewards accrued so far plus those projected until the end. 
+    pub fn set_solvency_check<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        farm_id: Arg0,
+        enabled: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setSolvencyCheck")
+            .argument(&farm_id)
+            .argument(&enabled)
+            .original_result()
+    }
+
+    pub fn get_farm_info<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, structs::Farm<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFarmInfo")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    /// Lists up to `size` farms, starting at the zero-based position `from`. 
+    pub fn get_farms<
+        Arg0: ProxyArg<usize>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        from: Arg0,
+        size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, structs::Farm<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFarms")
+            .argument(&from)
+            .argument(&size)
+            .original_result()
+    }
+
+    /// Default destination of `withdrawRewards`, the owner until a treasury is set. 
+    pub fn get_treasury(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTreasury")
+            .original_result()
+    }
+
+    pub fn get_farm_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFarmCount")
+            .original_result()
+    }
+
+    pub fn get_reward_info<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        reward_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, structs::RewardInfo<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRewardInfo")
+            .argument(&farm_id)
+            .argument(&reward_token)
+            .original_result()
+    }
+
+    pub fn get_rate_history<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        reward_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, structs::RateCheckpoint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRateHistory")
+            .argument(&farm_id)
+            .argument(&reward_token)
+            .original_result()
+    }
+
+    /// Rewards accrued to the staked positions and not paid out yet. 
+    pub fn get_accrued_rewards_view<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        farm_id: Arg0,
+        opt_reward_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAccruedRewards")
+            .argument(&farm_id)
+            .argument(&opt_reward_token)
+            .original_result()
+    }
+
+    /// Accrued rewards plus those emitted until the end at the current rate and supply. 
+    pub fn get_projected_obligations_view<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        farm_id: Arg0,
+        opt_reward_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProjectedObligations")
+            .argument(&farm_id)
+            .argument(&opt_reward_token)
+            .original_result()
+    }
+
+    /// Seconds of emission at the current rate and supply that the reserve covers on top of the 
+    /// accrued rewards, counted from the start if the farm has not started yet, `u64::MAX` if 
+    /// nothing is emitted. 
+    pub fn get_rewards_runway<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        farm_id: Arg0,
+        opt_reward_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRewardsRunway")
+            .argument(&farm_id)
+            .argument(&opt_reward_token)
+            .original_result()
+    }
+
+    pub fn last_farm_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastFarmId")
+            .original_result()
+    }
+
+    pub fn share_token_farm_id<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        share_token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFarmIdByShareToken")
+            .argument(&share_token)
+            .original_result()
+    }
+
+    pub fn reward_tokens<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EgldOrEsdtTokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRewardTokens")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn total_staked<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalStaked")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn total_share_supply<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalShareSupply")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    /// Share supply weighted by the lock multipliers, which `Pool` farms distribute over. 
+    pub fn boosted_share_supply<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBoostedShareSupply")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn withdrawal_destinations(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWithdrawalDestinations")
+            .original_result()
+    }
+
+    pub fn solvency_check_enabled<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isSolvencyCheckEnabled")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn user_staked<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUserStaked")
+            .argument(&farm_id)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn reward_fee_bps(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRewardFee")
+            .original_result()
+    }
+
+    pub fn stake_fee_bps(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStakeFee")
+            .original_result()
+    }
+
+    /// Queued as a timelock action while a timelock delay is set. 
+    pub fn set_protocol_fees<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        reward_fee_bps: Arg0,
+        stake_fee_bps: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setProtocolFees")
+            .argument(&reward_fee_bps)
+            .argument(&stake_fee_bps)
+            .original_result()
+    }
+
+    pub fn set_fee_collector<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        fee_collector: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeCollector")
+            .argument(&fee_collector)
+            .original_result()
+    }
+
+    /// Sends the accrued fees of `tokens`, or of every token if none is given, to the fee collector. 
+    pub fn withdraw_fees<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        tokens: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawFees")
+            .argument(&tokens)
+            .original_result()
+    }
+
+    /// Destination of `withdrawFees`, the treasury until a fee collector is set. 
+    pub fn get_fee_collector(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeCollector")
+            .original_result()
+    }
+
+    pub fn get_accrued_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EgldOrEsdtTokenPayment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAccruedFees")
+            .original_result()
+    }
+
+    pub fn set_lock_tier<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+        lock_period_ms: Arg1,
+        multiplier_bps: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setLockTier")
+            .argument(&farm_id)
+            .argument(&lock_period_ms)
+            .argument(&multiplier_bps)
+            .original_result()
+    }
+
+    pub fn remove_lock_tier<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+        lock_period_ms: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeLockTier")
+            .argument(&farm_id)
+            .argument(&lock_period_ms)
+            .original_result()
+    }
+
+    /// New stakes stay locked for at least `min_stake_duration_ms`, without any boost; `0` disables it. 
+    pub fn set_min_stake_duration<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+        min_stake_duration_ms: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMinStakeDuration")
+            .argument(&farm_id)
+            .argument(&min_stake_duration_ms)
+            .original_result()
+    }
+
+    pub fn get_lock_tiers<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, structs::LockTier>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLockTiers")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn get_min_stake_duration<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinStakeDuration")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn stake<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        farm_id: Arg0,
+        opt_lock_period_ms: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("stake")
+            .argument(&farm_id)
+            .argument(&opt_lock_period_ms)
+            .original_result()
+    }
+
+    /// Stakes the caller's tokens into a position owned by `recipient`. 
+    pub fn stake_for<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        farm_id: Arg0,
+        recipient: Arg1,
+        opt_lock_period_ms: Arg2,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("stakeFor")
+            .argument(&farm_id)
+            .argument(&recipient)
+            .argument(&opt_lock_period_ms)
+            .original_result()
+    }
+
+    pub fn unstake<
+        Arg0: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+    >(
+        self,
+        opt_unstake_amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("unstake")
+            .argument(&opt_unstake_amount)
+            .original_result()
+    }
+
+    /// Unstakes the caller's position, delivering the principal and rewards to `recipient`. 
+    pub fn unstake_to<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+    >(
+        self,
+        recipient: Arg0,
+        opt_unstake_amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("unstakeTo")
+            .argument(&recipient)
+            .argument(&opt_unstake_amount)
+            .original_result()
+    }
+
+    /// Burns the share tokens and returns their principal, forfeiting their rewards to the 
+    /// reserve. The principal goes through the farm's unbonding period unless emergency mode is 
+    /// on, which also lets locked positions exit and keeps the endpoint available while paused. 
+    pub fn emergency_unstake(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("emergencyUnstake")
+            .original_result()
+    }
+
+    /// Stakes back the tokens of a pending unbonding into a new share token, free of the stake 
+    /// fee they already paid. 
+    pub fn cancel_unbonding<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        farm_id: Arg0,
+        unbonding_id: Arg1,
+        opt_lock_period_ms: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelUnbonding")
+            .argument(&farm_id)
+            .argument(&unbonding_id)
+            .argument(&opt_lock_period_ms)
+            .original_result()
+    }
+
+    pub fn claim_rewards_endpoint<
+        Arg0: ProxyArg<OptionalValue<bool>>,
+    >(
+        self,
+        opt_compound: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("claimRewards")
+            .argument(&opt_compound)
+            .original_result()
+    }
+
+    /// Claims the rewards of the caller's share tokens into `recipient`; the share tokens go back to the caller. 
+    pub fn claim_rewards_to<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        recipient: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("claimRewardsTo")
+            .argument(&recipient)
+            .original_result()
+    }
+
+    pub fn compound(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("compound")
+            .original_result()
+    }
+
+    /// Pays the caller's rewards left unpaid by a short reserve, as far as the reserve now allows. 
+    pub fn claim_unpaid_rewards<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimUnpaidRewards")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    /// Takes the boost of a `Pool` share token nonce off the boosted share supply once its lock has 
+    /// ended, so that it stops diluting the other stakers. Anyone can call it. 
+    pub fn settle_expired_boost_endpoint<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("settleExpiredBoost")
+            .argument(&farm_id)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    /// Rewards of the share tokens net of the reward fee; a short reserve may pay less. 
+    pub fn get_claimable_rewards<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<u64, BigUint<Env::Api>>>>,
+    >(
+        self,
+        farm_id: Arg0,
+        address: Arg1,
+        share_tokens: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EgldOrEsdtTokenPayment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getClaimableRewards")
+            .argument(&farm_id)
+            .argument(&address)
+            .argument(&share_tokens)
+            .original_result()
+    }
+
+    pub fn get_unpaid_rewards<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EgldOrEsdtTokenPayment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnpaidRewards")
+            .argument(&farm_id)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn set_early_unstake_penalty<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<structs::PenaltySource>,
+        Arg3: ProxyArg<structs::PenaltyDestination<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        penalty_bps: Arg1,
+        source: Arg2,
+        destination: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setEarlyUnstakePenalty")
+            .argument(&farm_id)
+            .argument(&penalty_bps)
+            .argument(&source)
+            .argument(&destination)
+            .original_result()
+    }
+
+    pub fn remove_early_unstake_penalty<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeEarlyUnstakePenalty")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn get_early_unstake_penalty_config<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<structs::EarlyUnstakePenalty<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEarlyUnstakePenaltyConfig")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    /// Penalty charged if `amount` of the share token `nonce` held by `address` was unstaked now, 
+    /// a rewards penalty being capped by what the reserve holds once the rewards are paid. 
+    pub fn get_early_unstake_penalty<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        address: Arg1,
+        nonce: Arg2,
+        amount: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EgldOrEsdtTokenPayment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEarlyUnstakePenalty")
+            .argument(&farm_id)
+            .argument(&address)
+            .argument(&nonce)
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn set_unbonding_period<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+        unbonding_period_ms: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setUnbondingPeriod")
+            .argument(&farm_id)
+            .argument(&unbonding_period_ms)
+            .original_result()
+    }
+
+    pub fn withdraw<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdraw")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn get_unbonding_period<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnbondingPeriod")
+            .argument(&farm_id)
+            .original_result()
+    }
+
+    pub fn get_unbondings<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        farm_id: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, structs::Unbonding<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUnbondings")
+            .argument(&farm_id)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_total_unbonding<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        farm_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalUnbonding")
+            .argument(&farm_id)
             .original_result()
     }
 }
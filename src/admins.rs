@@ -1,20 +1,84 @@
-use errors::ERROR_NOT_ADMIN;
+use errors::{
+    ERROR_ACTION_ALREADY_APPROVED, ERROR_INVALID_APPROVAL_THRESHOLD, ERROR_MISSING_ROLE,
+    ERROR_NOT_ADMIN, ERROR_NOT_OWNER, ERROR_NOT_PENDING_OWNER, ERROR_NO_PENDING_OWNER,
+    ERROR_UPGRADE_RIGHTS_NOT_TRANSFERABLE,
+};
 use multiversx_sc::imports::*;
+use structs::Role;
 
-/// Smart Contract module that offers admin management capabilities.
+/// Smart Contract module that offers ownership, admin and role management capabilities.
 ///
 /// It provides:
+/// * two endpoints where the owner can propose a new owner, who then accepts the ownership
+/// * an endpoint where the owner can cancel a proposed transfer
 /// * two endpoints where the owner can add/remove admins
-/// * a view to check if an address is an admin
-/// * a view to get the list of admins
-/// * a method to require an address to be an admin
+/// * two endpoints where an admin can renounce being admin and a role holder its role
+/// * two endpoints where the owner can grant/revoke a role
+/// * an endpoint where the owner can set how many admins must approve a timelock action
+/// * views to check if an address is an admin or holds a role
+/// * views to get the list of admins, the members of a role and the approvers of an action
+/// * methods to require an address to be the owner, an admin or to hold a role
+///
+/// The owner starts as the deployer. Accepting the ownership also hands over the upgrade rights
+/// when the contract is its own owner on chain, as only that owner may change it; otherwise the
+/// new owner must already own the contract on chain, so that both owners never diverge. Admins approve timelock actions; every
+/// other permission is a role the owner grants explicitly, admins included.
 #[multiversx_sc::module]
 pub trait AdminsModule {
     // === Endpoints ===
 
-    #[only_owner]
+    /// Makes `new_owner` the pending owner, replacing any previous proposal.
+    #[endpoint(proposeOwner)]
+    fn propose_owner(&self, new_owner: ManagedAddress) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        self.pending_owner().set(&new_owner);
+
+        self.event_owner_proposed(&self.get_owner(), &new_owner);
+    }
+
+    #[endpoint(cancelOwnershipTransfer)]
+    fn cancel_ownership_transfer(&self) {
+        self.require_is_owner(&self.blockchain().get_caller());
+        require!(!self.pending_owner().is_empty(), ERROR_NO_PENDING_OWNER);
+
+        let pending_owner = self.pending_owner().take();
+
+        self.event_ownership_transfer_cancelled(&self.get_owner(), &pending_owner);
+    }
+
+    #[endpoint(acceptOwnership)]
+    fn accept_ownership(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.pending_owner().is_empty() && self.pending_owner().get() == caller,
+            ERROR_NOT_PENDING_OWNER
+        );
+
+        let sc_address = self.blockchain().get_sc_address();
+        let on_chain_owner = self.blockchain().get_owner_address();
+        require!(
+            on_chain_owner == sc_address || on_chain_owner == caller,
+            ERROR_UPGRADE_RIGHTS_NOT_TRANSFERABLE
+        );
+
+        let previous_owner = self.get_owner();
+        self.owner().set(&caller);
+        self.pending_owner().clear();
+
+        if on_chain_owner == sc_address {
+            self.send()
+                .change_owner_address(sc_address, &caller)
+                .sync_call();
+        }
+
+        self.event_ownership_transferred(&previous_owner, &caller);
+    }
+
     #[endpoint(addAdmins)]
     fn add_admin(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
         self.event_admins_added(&addresses);
 
         for address in addresses.into_iter() {
@@ -22,40 +86,233 @@ pub trait AdminsModule {
         }
     }
 
-    #[only_owner]
     #[endpoint(removeAdmins)]
     fn remove_admin(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
         self.event_admins_removed(&addresses);
 
         for address in addresses.into_iter() {
             self.admins().swap_remove(&address);
         }
+
+        self.require_valid_approval_threshold(self.approval_threshold().get());
+    }
+
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        self.event_role_granted(role, &addresses);
+
+        for address in addresses.into_iter() {
+            self.role_members(role).insert(address);
+        }
+    }
+
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        self.event_role_revoked(role, &addresses);
+
+        for address in addresses.into_iter() {
+            self.role_members(role).swap_remove(&address);
+        }
+    }
+
+    #[endpoint(renounceAdmin)]
+    fn renounce_admin(&self) {
+        let caller = self.blockchain().get_caller();
+        self.require_is_admin(&caller);
+
+        self.admins().swap_remove(&caller);
+        self.require_valid_approval_threshold(self.approval_threshold().get());
+
+        self.event_admin_renounced(&caller);
+    }
+
+    #[endpoint(renounceRole)]
+    fn renounce_role(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.role_members(role).swap_remove(&caller),
+            ERROR_MISSING_ROLE
+        );
+
+        self.event_role_renounced(role, &caller);
+    }
+
+    /// Number of admin approvals a timelock action needs before it can be executed; `0` or `1`
+    /// lets the proposer's role alone authorize it. Admins can't then be removed below it.
+    #[endpoint(setApprovalThreshold)]
+    fn set_approval_threshold(&self, threshold: usize) {
+        self.require_is_owner(&self.blockchain().get_caller());
+        self.require_valid_approval_threshold(threshold);
+
+        self.approval_threshold().set(threshold);
+
+        self.event_approval_threshold_set(threshold);
     }
 
     // === Views ===
 
+    #[view(getOwner)]
+    fn get_owner(&self) -> ManagedAddress {
+        if self.owner().is_empty() {
+            return self.blockchain().get_owner_address();
+        }
+
+        self.owner().get()
+    }
+
     #[view(isAdmin)]
     fn is_admin(&self, address: &ManagedAddress) -> bool {
         self.admins().contains(address)
     }
 
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: &ManagedAddress) -> bool {
+        self.role_members(role).contains(address)
+    }
+
+    #[view(getPendingOwner)]
+    fn get_pending_owner(&self) -> OptionalValue<ManagedAddress> {
+        if self.pending_owner().is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(self.pending_owner().get())
+    }
+
     // === Private ===
 
+    fn require_is_owner(&self, address: &ManagedAddress) {
+        require!(*address == self.get_owner(), ERROR_NOT_OWNER);
+    }
+
     fn require_is_admin(&self, address: &ManagedAddress) {
         require!(self.is_admin(address), ERROR_NOT_ADMIN);
     }
 
+    fn require_has_role(&self, role: Role, address: &ManagedAddress) {
+        require!(self.has_role(role, address), ERROR_MISSING_ROLE);
+    }
+
+    fn require_valid_approval_threshold(&self, threshold: usize) {
+        require!(
+            threshold <= self.admins().len(),
+            ERROR_INVALID_APPROVAL_THRESHOLD
+        );
+    }
+
+    fn requires_approvals(&self) -> bool {
+        self.approval_threshold().get() > 1
+    }
+
+    fn record_approval(&self, action_id: u64, address: &ManagedAddress) {
+        require!(
+            self.action_approvers(action_id).insert(address.clone()),
+            ERROR_ACTION_ALREADY_APPROVED
+        );
+
+        self.event_action_approved(action_id, address);
+    }
+
+    /// Only the approvers still admin count towards the threshold.
+    fn has_approval_quorum(&self, action_id: u64) -> bool {
+        if !self.requires_approvals() {
+            return true;
+        }
+
+        let approvals = self
+            .action_approvers(action_id)
+            .iter()
+            .filter(|approver| self.is_admin(approver))
+            .count();
+        approvals >= self.approval_threshold().get()
+    }
+
     // === Storage ===
 
+    #[storage_mapper("owner")]
+    fn owner(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("pending_owner")]
+    fn pending_owner(&self) -> SingleValueMapper<ManagedAddress>;
+
     #[view(getAdmins)]
     #[storage_mapper("admins")]
     fn admins(&self) -> UnorderedSetMapper<ManagedAddress>;
 
+    #[view(getRoleMembers)]
+    #[storage_mapper("role_members")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getApprovalThreshold)]
+    #[storage_mapper("approval_threshold")]
+    fn approval_threshold(&self) -> SingleValueMapper<usize>;
+
+    #[view(getActionApprovers)]
+    #[storage_mapper("action_approvers")]
+    fn action_approvers(&self, action_id: u64) -> UnorderedSetMapper<ManagedAddress>;
+
     // === Events ===
 
+    #[event("ownerProposed")]
+    fn event_owner_proposed(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] pending_owner: &ManagedAddress,
+    );
+
+    #[event("ownershipTransferCancelled")]
+    fn event_ownership_transfer_cancelled(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] pending_owner: &ManagedAddress,
+    );
+
+    #[event("ownershipTransferred")]
+    fn event_ownership_transferred(
+        &self,
+        #[indexed] previous_owner: &ManagedAddress,
+        #[indexed] new_owner: &ManagedAddress,
+    );
+
     #[event("adminsAdded")]
     fn event_admins_added(&self, #[indexed] admins: &MultiValueEncoded<ManagedAddress>);
 
     #[event("adminsRemoved")]
     fn event_admins_removed(&self, #[indexed] admins: &MultiValueEncoded<ManagedAddress>);
+
+    #[event("roleGranted")]
+    fn event_role_granted(
+        &self,
+        #[indexed] role: Role,
+        #[indexed] addresses: &MultiValueEncoded<ManagedAddress>,
+    );
+
+    #[event("roleRevoked")]
+    fn event_role_revoked(
+        &self,
+        #[indexed] role: Role,
+        #[indexed] addresses: &MultiValueEncoded<ManagedAddress>,
+    );
+
+    #[event("approvalThresholdSet")]
+    fn event_approval_threshold_set(&self, #[indexed] threshold: usize);
+
+    #[event("actionApproved")]
+    fn event_action_approved(
+        &self,
+        #[indexed] action_id: u64,
+        #[indexed] approver: &ManagedAddress,
+    );
+
+    #[event("adminRenounced")]
+    fn event_admin_renounced(&self, #[indexed] admin: &ManagedAddress);
+
+    #[event("roleRenounced")]
+    fn event_role_renounced(&self, #[indexed] role: Role, #[indexed] address: &ManagedAddress);
 }
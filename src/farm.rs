@@ -1,17 +1,21 @@
-use constants::{TOKEN_ISSUANCE_COST, WAD_DECIMALS};
+use constants::{MAX_REWARD_TOKENS, TOKEN_ISSUANCE_COST, WAD, WAD_DECIMALS};
 use errors::{
-    ERROR_ALREADY_ENDED, ERROR_ALREADY_STARTED, ERROR_FARM_ALREADY_CREATED,
-    ERROR_INSUFFICIENT_RESERVE, ERROR_INVALID_REWARD_TOKEN, ERROR_WRONG_ISSUANCE_AMOUNT,
-    ERROR_WRONG_REWARD_VALUES, ERROR_WRONG_START_TS, ERROR_WRONG_TIMEFRAME, ERROR_ZERO_AMOUNT,
+    ERROR_ALREADY_ENDED, ERROR_ALREADY_STARTED, ERROR_DESTINATION_NOT_WHITELISTED,
+    ERROR_FARM_NOT_FOUND, ERROR_INSUFFICIENT_RESERVE, ERROR_INVALID_REWARD_TOKEN,
+    ERROR_INVALID_SHARE_TOKEN, ERROR_RESERVE_BELOW_OBLIGATIONS, ERROR_REWARD_TOKEN_ALREADY_ADDED,
+    ERROR_TOO_MANY_REWARD_TOKENS, ERROR_WRONG_ISSUANCE_AMOUNT, ERROR_WRONG_REWARD_VALUES,
+    ERROR_WRONG_START_TS, ERROR_WRONG_TIMEFRAME, ERROR_ZERO_AMOUNT,
 };
 use multiversx_sc::imports::*;
-use structs::Farm;
+use structs::{
+    EarlyUnstakePenalty, EmissionMode, Farm, FarmId, RateCheckpoint, RewardInfo, Role,
+    TimelockAction, WithdrawalDestination,
+};
 
 #[multiversx_sc::module]
-pub trait FarmModule: crate::admins::AdminsModule {
+pub trait FarmModule: crate::admins::AdminsModule + crate::timelock::TimelockModule {
     // === Endpoints ===
 
-    #[only_owner]
     #[payable]
     #[endpoint(createFarm)]
     fn create_farm(
@@ -24,25 +28,21 @@ pub trait FarmModule: crate::admins::AdminsModule {
         reward_per_share: BigUint,
         share_token_display_name: ManagedBuffer,
         share_token_ticker: ManagedBuffer,
+        opt_emission_mode: OptionalValue<EmissionMode>,
     ) {
-        require!(
-            self.staked_token().is_empty()
-                && self.reward_token().is_empty()
-                && self.start_ts_ms().is_empty()
-                && self.end_ts_ms().is_empty()
-                && self.reward_per_sec().is_empty()
-                && self.reward_per_share().is_empty()
-                && self.share_token().is_empty(),
-            ERROR_FARM_ALREADY_CREATED
-        );
-        require!(start_ts_ms < end_ts_ms, ERROR_WRONG_TIMEFRAME);
-        require!(
-            start_ts_ms > self.blockchain().get_block_timestamp_ms(),
-            ERROR_WRONG_START_TS
-        );
-        require!(
-            &reward_per_sec * &reward_per_share > BigUint::zero(),
-            ERROR_WRONG_REWARD_VALUES
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        let emission_mode = opt_emission_mode
+            .into_option()
+            .unwrap_or(EmissionMode::FixedRate);
+
+        self.require_valid_reward_token_config(
+            emission_mode,
+            &reward_token,
+            start_ts_ms,
+            end_ts_ms,
+            &reward_per_sec,
+            &reward_per_share,
         );
 
         let egld_transferred = self.call_value().egld();
@@ -51,188 +51,1198 @@ pub trait FarmModule: crate::admins::AdminsModule {
             ERROR_WRONG_ISSUANCE_AMOUNT
         );
 
-        self.share_token().issue_and_set_all_roles(
+        let farm_id = self.last_farm_id().update(|id| {
+            *id += 1;
+            *id
+        });
+
+        self.share_token(farm_id).issue_and_set_all_roles(
             EsdtTokenType::DynamicMeta,
             BigUint::from(TOKEN_ISSUANCE_COST),
             share_token_display_name,
             share_token_ticker,
             WAD_DECIMALS,
             Option::Some(self.callbacks().share_token_issuance_callback(
-                &self.blockchain().get_owner_address(),
+                farm_id,
+                &self.get_owner(),
                 &staked_token,
                 &reward_token,
                 start_ts_ms,
                 end_ts_ms,
                 &reward_per_sec,
                 &reward_per_share,
+                emission_mode,
             )),
         );
     }
 
+    #[endpoint(addRewardToken)]
+    fn add_reward_token(
+        &self,
+        farm_id: FarmId,
+        reward_token: EgldOrEsdtTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: BigUint,
+        reward_per_share: BigUint,
+    ) {
+        self.require_has_role(Role::RateManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        require!(
+            !self.reward_tokens(farm_id).contains(&reward_token),
+            ERROR_REWARD_TOKEN_ALREADY_ADDED
+        );
+        require!(
+            self.reward_tokens(farm_id).len() < MAX_REWARD_TOKENS,
+            ERROR_TOO_MANY_REWARD_TOKENS
+        );
+        self.require_valid_reward_token_config(
+            self.emission_mode(farm_id).get(),
+            &reward_token,
+            start_ts_ms,
+            end_ts_ms,
+            &reward_per_sec,
+            &reward_per_share,
+        );
+
+        self.register_reward_token(
+            farm_id,
+            &reward_token,
+            start_ts_ms,
+            end_ts_ms,
+            &reward_per_sec,
+            &reward_per_share,
+        );
+
+        self.event_reward_token_added(
+            farm_id,
+            &reward_token,
+            start_ts_ms,
+            end_ts_ms,
+            &reward_per_sec,
+            &reward_per_share,
+        );
+    }
+
     #[endpoint(modifyStartTs)]
-    fn modify_start_ts(&self, new_start_ts_ms: u64) {
-        self.require_is_admin(&self.blockchain().get_caller());
+    fn modify_start_ts(
+        &self,
+        farm_id: FarmId,
+        new_start_ts_ms: u64,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) {
+        self.require_has_role(Role::ScheduleManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        let reward_token = self.resolve_reward_token(farm_id, opt_reward_token);
 
         let current_ts_ms = self.blockchain().get_block_timestamp_ms();
         require!(
-            self.start_ts_ms().get() > current_ts_ms,
+            self.start_ts_ms(farm_id, &reward_token).get() > current_ts_ms,
             ERROR_ALREADY_STARTED
         );
         require!(new_start_ts_ms > current_ts_ms, ERROR_WRONG_START_TS);
+        require!(
+            new_start_ts_ms < self.end_ts_ms(farm_id, &reward_token).get(),
+            ERROR_WRONG_TIMEFRAME
+        );
 
-        self.start_ts_ms().set(new_start_ts_ms);
+        self.start_ts_ms(farm_id, &reward_token)
+            .set(new_start_ts_ms);
 
-        self.event_start_ts_modified(new_start_ts_ms);
+        self.event_start_ts_modified(farm_id, &reward_token, new_start_ts_ms);
     }
 
+    /// Queued as a timelock action while a timelock delay is set.
     #[endpoint(modifyEndTs)]
-    fn modify_end_ts(&self, new_end_ts_ms: u64) {
-        self.require_is_admin(&self.blockchain().get_caller());
-
-        let current_ts_ms = self.blockchain().get_block_timestamp_ms();
-        require!(self.end_ts_ms().get() > current_ts_ms, ERROR_ALREADY_ENDED);
-        require!(new_end_ts_ms > current_ts_ms, ERROR_WRONG_TIMEFRAME);
+    fn modify_end_ts(
+        &self,
+        farm_id: FarmId,
+        new_end_ts_ms: u64,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) {
+        self.require_has_role(Role::ScheduleManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
 
-        self.end_ts_ms().set(new_end_ts_ms);
+        let reward_token = self.resolve_reward_token(farm_id, opt_reward_token);
 
-        self.event_end_ts_modified(new_end_ts_ms);
+        self.queue_or_apply(TimelockAction::ModifyEndTs {
+            farm_id,
+            reward_token,
+            new_end_ts_ms,
+        });
     }
 
+    /// Queued as a timelock action while a timelock delay is set.
     #[endpoint(modifyRewards)]
-    fn modify_rewards(&self, new_reward_per_sec: BigUint, new_reward_per_share: BigUint) {
-        self.require_is_admin(&self.blockchain().get_caller());
+    fn modify_rewards(
+        &self,
+        farm_id: FarmId,
+        new_reward_per_sec: BigUint,
+        new_reward_per_share: BigUint,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) {
+        self.require_has_role(Role::RateManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
 
-        let current_ts_ms = self.blockchain().get_block_timestamp_ms();
-        require!(self.end_ts_ms().get() > current_ts_ms, ERROR_ALREADY_ENDED);
-        require!(
-            &new_reward_per_sec * &new_reward_per_share > BigUint::zero(),
-            ERROR_WRONG_REWARD_VALUES
+        let reward_token = self.resolve_reward_token(farm_id, opt_reward_token);
+        self.require_valid_reward_values(
+            self.emission_mode(farm_id).get(),
+            &new_reward_per_sec,
+            &new_reward_per_share,
         );
 
-        self.reward_per_sec().set(&new_reward_per_sec);
-        self.reward_per_share().set(&new_reward_per_share);
-
-        self.event_rewards_modified(&new_reward_per_sec, &new_reward_per_share);
+        self.queue_or_apply(TimelockAction::ModifyRewards {
+            farm_id,
+            reward_token,
+            new_reward_per_sec,
+            new_reward_per_share,
+        });
     }
 
     #[payable]
     #[endpoint(depositRewards)]
-    fn deposit_rewards(&self) {
-        self.require_is_admin(&self.blockchain().get_caller());
+    fn deposit_rewards(&self, farm_id: FarmId) {
+        self.require_has_role(Role::Treasurer, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
 
         let (token, amount) = self.call_value().egld_or_single_fungible_esdt();
         require!(
-            token == self.reward_token().get(),
+            self.reward_tokens(farm_id).contains(&token),
             ERROR_INVALID_REWARD_TOKEN
         );
         require!(amount > BigUint::zero(), ERROR_ZERO_AMOUNT);
 
-        self.rewards_reserve().update(|c| *c += &amount);
+        self.rewards_reserve(farm_id, &token)
+            .update(|c| *c += &amount);
 
-        self.event_rewards_deposited(&amount);
+        self.event_rewards_deposited(farm_id, &token, &amount);
     }
 
+    /// Sends `amount` of the reserve to the treasury, or to `opt_to` if it is a whitelisted
+    /// destination, which also requires the reward token to be given; queued as a timelock
+    /// action while a timelock delay is set, keeping the treasury of the time it was queued and
+    /// checking a whitelisted destination again on execution.
+    #[allow_multiple_var_args]
     #[endpoint(withdrawRewards)]
-    fn withdraw_rewards(&self, amount: BigUint) {
-        self.require_is_admin(&self.blockchain().get_caller());
+    fn withdraw_rewards(
+        &self,
+        farm_id: FarmId,
+        amount: BigUint,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+        opt_to: OptionalValue<ManagedAddress>,
+    ) {
+        self.require_has_role(Role::Treasurer, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
 
+        let reward_token = self.resolve_reward_token(farm_id, opt_reward_token);
         require!(amount > BigUint::zero(), ERROR_ZERO_AMOUNT);
 
-        let current_reserve = self.rewards_reserve().get();
-        require!(amount <= current_reserve, ERROR_INSUFFICIENT_RESERVE);
+        let to = match opt_to.into_option() {
+            Some(to) => WithdrawalDestination::Whitelisted(to),
+            None => WithdrawalDestination::Treasury(self.get_treasury()),
+        };
+        self.resolve_withdrawal_destination(&to);
 
-        self.rewards_reserve().set(&current_reserve - &amount);
+        self.queue_or_apply(TimelockAction::WithdrawRewards {
+            farm_id,
+            reward_token,
+            amount,
+            to,
+        });
+    }
 
-        self.send().direct(
-            &self.blockchain().get_owner_address(),
-            &self.reward_token().get(),
-            0,
-            &amount,
-        );
+    /// Applies a pending timelock action once its delay has elapsed and its approvals reached the
+    /// threshold; anyone can call it.
+    #[endpoint(executeAction)]
+    fn execute_action(&self, action_id: u64) {
+        let action = self.take_ready_action(action_id);
 
-        self.event_rewards_withdrawn(&amount);
+        self.apply_action(action);
+    }
+
+    /// Approves a pending timelock action, which is then applied through `executeAction`.
+    #[endpoint(approveAction)]
+    fn approve_action(&self, action_id: u64) {
+        let caller = self.blockchain().get_caller();
+        self.require_is_admin(&caller);
+        self.require_action_pending(action_id);
+
+        self.record_approval(action_id, &caller);
+    }
+
+    #[endpoint(setTreasury)]
+    fn set_treasury(&self, treasury: ManagedAddress) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        self.treasury().set(&treasury);
+
+        self.event_treasury_set(&treasury);
+    }
+
+    #[endpoint(addWithdrawalDestinations)]
+    fn add_withdrawal_destinations(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        self.event_withdrawal_destinations_added(&addresses);
+
+        for address in addresses.into_iter() {
+            self.withdrawal_destinations().insert(address);
+        }
+    }
+
+    #[endpoint(removeWithdrawalDestinations)]
+    fn remove_withdrawal_destinations(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        self.event_withdrawal_destinations_removed(&addresses);
+
+        for address in addresses.into_iter() {
+            self.withdrawal_destinations().swap_remove(&address);
+        }
+    }
+
+    /// When enabled, `modifyRewards`, `modifyEndTs` and `withdrawRewards` are refused if they
+    /// leave a reserve below the rewards accrued so far plus those projected until the end.
+    #[endpoint(setSolvencyCheck)]
+    fn set_solvency_check(&self, farm_id: FarmId, enabled: bool) {
+        self.require_has_role(Role::Treasurer, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        self.solvency_check_enabled(farm_id).set(enabled);
+
+        self.event_solvency_check_set(farm_id, enabled);
     }
 
     // === Views ===
 
     #[view(getFarmInfo)]
-    fn get_farm_info(&self) -> Farm<Self::Api> {
+    fn get_farm_info(&self, farm_id: FarmId) -> Farm<Self::Api> {
+        self.require_farm_exists(farm_id);
+
+        let mut rewards = ManagedVec::new();
+        for reward_token in self.reward_tokens(farm_id).iter() {
+            rewards.push(self.get_reward_info(farm_id, reward_token));
+        }
+
         Farm {
-            staked_token: self.staked_token().get(),
-            share_token: self.share_token().get_token_id(),
-            reward_token: self.reward_token().get(),
-            rewards_reserve: self.rewards_reserve().get(),
-            start_ts_ms: self.start_ts_ms().get(),
-            end_ts_ms: self.end_ts_ms().get(),
-            reward_per_sec: self.reward_per_sec().get(),
-            reward_per_share: self.reward_per_share().get(),
+            farm_id,
+            staked_token: self.staked_token(farm_id).get(),
+            share_token: self.share_token(farm_id).get_token_id(),
+            emission_mode: self.emission_mode(farm_id).get(),
+            total_staked: self.total_staked(farm_id).get(),
+            total_share_supply: self.total_share_supply(farm_id).get(),
+            rewards,
+        }
+    }
+
+    /// Lists up to `size` farms, starting at the zero-based position `from`.
+    #[view(getFarms)]
+    fn get_farms(&self, from: usize, size: usize) -> MultiValueEncoded<Farm<Self::Api>> {
+        let mut farms = MultiValueEncoded::new();
+        for farm_id in self.farm_ids().iter().skip(from).take(size) {
+            farms.push(self.get_farm_info(farm_id));
+        }
+
+        farms
+    }
+
+    /// Default destination of `withdrawRewards`, the owner until a treasury is set.
+    #[view(getTreasury)]
+    fn get_treasury(&self) -> ManagedAddress {
+        if self.treasury().is_empty() {
+            return self.get_owner();
+        }
+
+        self.treasury().get()
+    }
+
+    #[view(getFarmCount)]
+    fn get_farm_count(&self) -> usize {
+        self.farm_ids().len()
+    }
+
+    #[view(getRewardInfo)]
+    fn get_reward_info(
+        &self,
+        farm_id: FarmId,
+        reward_token: EgldOrEsdtTokenIdentifier,
+    ) -> RewardInfo<Self::Api> {
+        require!(
+            self.reward_tokens(farm_id).contains(&reward_token),
+            ERROR_INVALID_REWARD_TOKEN
+        );
+
+        RewardInfo {
+            rewards_reserve: self.rewards_reserve(farm_id, &reward_token).get(),
+            start_ts_ms: self.start_ts_ms(farm_id, &reward_token).get(),
+            end_ts_ms: self.end_ts_ms(farm_id, &reward_token).get(),
+            reward_per_sec: self.reward_per_sec(farm_id, &reward_token).get(),
+            reward_per_share: self.reward_per_share(farm_id, &reward_token).get(),
+            reward_token,
+        }
+    }
+
+    #[view(getRateHistory)]
+    fn get_rate_history(
+        &self,
+        farm_id: FarmId,
+        reward_token: EgldOrEsdtTokenIdentifier,
+    ) -> MultiValueEncoded<RateCheckpoint<Self::Api>> {
+        let mut history = MultiValueEncoded::new();
+        for checkpoint in self.rate_history(farm_id, &reward_token).iter() {
+            history.push(checkpoint);
+        }
+
+        history
+    }
+
+    /// Rewards accrued to the staked positions and not paid out yet.
+    #[view(getAccruedRewards)]
+    fn get_accrued_rewards_view(
+        &self,
+        farm_id: FarmId,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> BigUint {
+        self.require_farm_exists(farm_id);
+
+        let reward_token = self.resolve_reward_token(farm_id, opt_reward_token);
+        self.get_accrued_rewards(
+            farm_id,
+            &reward_token,
+            self.blockchain().get_block_timestamp_ms(),
+        )
+    }
+
+    /// Accrued rewards plus those emitted until the end at the current rate and supply.
+    #[view(getProjectedObligations)]
+    fn get_projected_obligations_view(
+        &self,
+        farm_id: FarmId,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> BigUint {
+        self.require_farm_exists(farm_id);
+
+        let reward_token = self.resolve_reward_token(farm_id, opt_reward_token);
+        self.get_projected_obligations(
+            farm_id,
+            &reward_token,
+            self.blockchain().get_block_timestamp_ms(),
+        )
+    }
+
+    /// Seconds of emission at the current rate and supply that the reserve covers on top of the
+    /// accrued rewards, counted from the start if the farm has not started yet, `u64::MAX` if
+    /// nothing is emitted.
+    #[view(getRewardsRunway)]
+    fn get_rewards_runway(
+        &self,
+        farm_id: FarmId,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> u64 {
+        self.require_farm_exists(farm_id);
+
+        let reward_token = self.resolve_reward_token(farm_id, opt_reward_token);
+        let current_ts_ms = self.blockchain().get_block_timestamp_ms();
+        if current_ts_ms >= self.end_ts_ms(farm_id, &reward_token).get() {
+            return u64::MAX;
+        }
+
+        let (emission_per_sec, divisor) = self.get_emission_rate(farm_id, &reward_token);
+        if emission_per_sec == 0 {
+            return u64::MAX;
+        }
+
+        let from_ts_ms = core::cmp::max(
+            current_ts_ms,
+            self.start_ts_ms(farm_id, &reward_token).get(),
+        );
+        let reserve = self.rewards_reserve(farm_id, &reward_token).get();
+        let accrued_rewards = self.get_accrued_rewards(farm_id, &reward_token, from_ts_ms);
+        if reserve <= accrued_rewards {
+            return 0;
+        }
+
+        ((reserve - accrued_rewards) * divisor / emission_per_sec)
+            .to_u64()
+            .unwrap_or(u64::MAX)
+    }
+
+    // === Private ===
+
+    fn require_farm_exists(&self, farm_id: FarmId) {
+        require!(self.farm_ids().contains(&farm_id), ERROR_FARM_NOT_FOUND);
+    }
+
+    fn queue_or_apply(&self, action: TimelockAction<Self::Api>) {
+        if self.is_timelocked() || self.requires_approvals() {
+            self.queue_action(action);
+        } else {
+            self.apply_action(action);
+        }
+    }
+
+    fn apply_action(&self, action: TimelockAction<Self::Api>) {
+        match action {
+            TimelockAction::ModifyEndTs {
+                farm_id,
+                reward_token,
+                new_end_ts_ms,
+            } => self.apply_modify_end_ts(farm_id, &reward_token, new_end_ts_ms),
+            TimelockAction::ModifyRewards {
+                farm_id,
+                reward_token,
+                new_reward_per_sec,
+                new_reward_per_share,
+            } => self.apply_modify_rewards(
+                farm_id,
+                &reward_token,
+                &new_reward_per_sec,
+                &new_reward_per_share,
+            ),
+            TimelockAction::WithdrawRewards {
+                farm_id,
+                reward_token,
+                amount,
+                to,
+            } => self.apply_withdraw_rewards(
+                farm_id,
+                &reward_token,
+                &amount,
+                &self.resolve_withdrawal_destination(&to),
+            ),
+            TimelockAction::SetEarlyUnstakePenalty { farm_id, penalty } => {
+                self.apply_set_early_unstake_penalty(farm_id, penalty)
+            }
+            TimelockAction::SetTimelockDelay { delay_ms } => {
+                self.apply_set_timelock_delay(delay_ms)
+            }
+            TimelockAction::SetProtocolFees {
+                reward_fee_bps,
+                stake_fee_bps,
+            } => self.apply_set_protocol_fees(reward_fee_bps, stake_fee_bps),
+        }
+    }
+
+    fn apply_modify_end_ts(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        new_end_ts_ms: u64,
+    ) {
+        let current_ts_ms = self.blockchain().get_block_timestamp_ms();
+        require!(
+            self.end_ts_ms(farm_id, reward_token).get() > current_ts_ms,
+            ERROR_ALREADY_ENDED
+        );
+        require!(
+            new_end_ts_ms > current_ts_ms
+                && new_end_ts_ms > self.start_ts_ms(farm_id, reward_token).get(),
+            ERROR_WRONG_TIMEFRAME
+        );
+
+        self.end_ts_ms(farm_id, reward_token).set(new_end_ts_ms);
+        self.require_reserve_covers_obligations(farm_id, reward_token, current_ts_ms);
+
+        self.event_end_ts_modified(farm_id, reward_token, new_end_ts_ms);
+    }
+
+    fn apply_modify_rewards(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        new_reward_per_sec: &BigUint,
+        new_reward_per_share: &BigUint,
+    ) {
+        let current_ts_ms = self.blockchain().get_block_timestamp_ms();
+        require!(
+            self.end_ts_ms(farm_id, reward_token).get() > current_ts_ms,
+            ERROR_ALREADY_ENDED
+        );
+
+        self.update_acc_reward_per_share(farm_id, current_ts_ms);
+        self.push_rate_checkpoint(
+            farm_id,
+            reward_token,
+            current_ts_ms,
+            new_reward_per_sec,
+            new_reward_per_share,
+        );
+
+        self.reward_per_sec(farm_id, reward_token)
+            .set(new_reward_per_sec);
+        self.reward_per_share(farm_id, reward_token)
+            .set(new_reward_per_share);
+        self.require_reserve_covers_obligations(farm_id, reward_token, current_ts_ms);
+
+        self.event_rewards_modified(
+            farm_id,
+            reward_token,
+            new_reward_per_sec,
+            new_reward_per_share,
+        );
+    }
+
+    fn apply_withdraw_rewards(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        to: &ManagedAddress,
+    ) {
+        let current_reserve = self.rewards_reserve(farm_id, reward_token).get();
+        require!(*amount <= current_reserve, ERROR_INSUFFICIENT_RESERVE);
+
+        self.rewards_reserve(farm_id, reward_token)
+            .set(&current_reserve - amount);
+        self.require_reserve_covers_obligations(
+            farm_id,
+            reward_token,
+            self.blockchain().get_block_timestamp_ms(),
+        );
+
+        self.send().direct(to, reward_token, 0, amount);
+
+        self.event_rewards_withdrawn(farm_id, reward_token, amount, to);
+    }
+
+    fn resolve_withdrawal_destination(
+        &self,
+        to: &WithdrawalDestination<Self::Api>,
+    ) -> ManagedAddress {
+        match to {
+            WithdrawalDestination::Treasury(treasury) => treasury.clone(),
+            WithdrawalDestination::Whitelisted(to) => {
+                require!(
+                    self.withdrawal_destinations().contains(to),
+                    ERROR_DESTINATION_NOT_WHITELISTED
+                );
+                to.clone()
+            }
         }
     }
 
+    fn apply_set_early_unstake_penalty(
+        &self,
+        farm_id: FarmId,
+        penalty: EarlyUnstakePenalty<Self::Api>,
+    ) {
+        self.event_early_unstake_penalty_set(farm_id, &penalty);
+        self.early_unstake_penalty(farm_id).set(penalty);
+    }
+
+    fn apply_set_protocol_fees(&self, reward_fee_bps: u64, stake_fee_bps: u64) {
+        self.reward_fee_bps().set(reward_fee_bps);
+        self.stake_fee_bps().set(stake_fee_bps);
+
+        self.event_protocol_fees_set(reward_fee_bps, stake_fee_bps);
+    }
+
+    /// Resolves the farm a batch of share token transfers belongs to; all of them must be of the same farm.
+    fn get_farm_id_by_share_tokens(&self, transfers: &ManagedVec<EsdtTokenPayment>) -> FarmId {
+        require!(!transfers.is_empty(), ERROR_INVALID_SHARE_TOKEN);
+
+        let share_token = transfers.get(0).token_identifier.clone();
+        let farm_id_mapper = self.share_token_farm_id(&share_token);
+        require!(!farm_id_mapper.is_empty(), ERROR_INVALID_SHARE_TOKEN);
+
+        farm_id_mapper.get()
+    }
+
+    fn require_valid_reward_token_config(
+        &self,
+        emission_mode: EmissionMode,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: &BigUint,
+        reward_per_share: &BigUint,
+    ) {
+        require!(reward_token.is_valid(), ERROR_INVALID_REWARD_TOKEN);
+        require!(start_ts_ms < end_ts_ms, ERROR_WRONG_TIMEFRAME);
+        require!(
+            start_ts_ms > self.blockchain().get_block_timestamp_ms(),
+            ERROR_WRONG_START_TS
+        );
+        self.require_valid_reward_values(emission_mode, reward_per_sec, reward_per_share);
+    }
+
+    fn require_valid_reward_values(
+        &self,
+        emission_mode: EmissionMode,
+        reward_per_sec: &BigUint,
+        reward_per_share: &BigUint,
+    ) {
+        let valid = match emission_mode {
+            EmissionMode::FixedRate => reward_per_sec * reward_per_share > BigUint::zero(),
+            EmissionMode::Pool => *reward_per_sec > BigUint::zero(),
+        };
+        require!(valid, ERROR_WRONG_REWARD_VALUES);
+    }
+
+    fn register_reward_token(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: &BigUint,
+        reward_per_share: &BigUint,
+    ) {
+        let current_ts_ms = self.blockchain().get_block_timestamp_ms();
+
+        self.reward_tokens(farm_id).insert(reward_token.clone());
+        self.start_ts_ms(farm_id, reward_token).set(start_ts_ms);
+        self.end_ts_ms(farm_id, reward_token).set(end_ts_ms);
+        self.reward_per_sec(farm_id, reward_token)
+            .set(reward_per_sec);
+        self.reward_per_share(farm_id, reward_token)
+            .set(reward_per_share);
+        self.last_reward_ts_ms(farm_id, reward_token)
+            .set(current_ts_ms);
+        self.push_rate_checkpoint(
+            farm_id,
+            reward_token,
+            current_ts_ms,
+            reward_per_sec,
+            reward_per_share,
+        );
+    }
+
+    /// Defaults to the reward token the farm was created with.
+    fn resolve_reward_token(
+        &self,
+        farm_id: FarmId,
+        opt_reward_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> EgldOrEsdtTokenIdentifier {
+        match opt_reward_token {
+            OptionalValue::Some(reward_token) => {
+                require!(
+                    self.reward_tokens(farm_id).contains(&reward_token),
+                    ERROR_INVALID_REWARD_TOKEN
+                );
+                reward_token
+            }
+            OptionalValue::None => self.reward_tokens(farm_id).get_by_index(1),
+        }
+    }
+
+    /// Returns the milliseconds between `from_ts_ms` and `to_ts_ms` that fall inside the
+    /// reward token's `[start_ts_ms, end_ts_ms]` window, which is the only period that earns it.
+    fn get_rewarded_ms(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        from_ts_ms: u64,
+        to_ts_ms: u64,
+    ) -> u64 {
+        let from_ts_ms = core::cmp::max(from_ts_ms, self.start_ts_ms(farm_id, reward_token).get());
+        let to_ts_ms = core::cmp::min(to_ts_ms, self.end_ts_ms(farm_id, reward_token).get());
+
+        to_ts_ms.saturating_sub(from_ts_ms)
+    }
+
+    /// Returns the rewards accumulated per share unit (scaled by `WAD`) up to `timestamp_ms`.
+    ///
+    /// For `FixedRate` farms it is integrated from the rate history, so it is valid for any past
+    /// timestamp. For `Pool` farms it depends on the supply, so it is only valid from the last update on.
+    fn get_acc_reward_per_share(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+    ) -> BigUint {
+        match self.emission_mode(farm_id).get() {
+            EmissionMode::FixedRate => {
+                self.get_fixed_rate_acc_reward_per_share(farm_id, reward_token, timestamp_ms)
+            }
+            EmissionMode::Pool => {
+                self.get_pool_acc_reward_per_share(farm_id, reward_token, timestamp_ms)
+            }
+        }
+    }
+
+    fn get_fixed_rate_acc_reward_per_share(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+    ) -> BigUint {
+        match self.find_rate_checkpoint(farm_id, reward_token, timestamp_ms) {
+            Some(checkpoint) => {
+                let rewarded_ms = self.get_rewarded_ms(
+                    farm_id,
+                    reward_token,
+                    checkpoint.start_ts_ms,
+                    timestamp_ms,
+                );
+
+                checkpoint.acc_reward_per_share
+                    + checkpoint.reward_per_sec * rewarded_ms * BigUint::from(WAD)
+                        / (checkpoint.reward_per_share * 1000u64)
+            }
+            None => BigUint::zero(),
+        }
+    }
+
+    fn get_pool_acc_reward_per_share(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+    ) -> BigUint {
+        let acc_reward_per_share = self.acc_reward_per_share(farm_id, reward_token).get();
+
+        let boosted_share_supply = self.boosted_share_supply(farm_id).get();
+        if boosted_share_supply == 0 {
+            return acc_reward_per_share;
+        }
+
+        let rewarded_ms = self.get_rewarded_ms(
+            farm_id,
+            reward_token,
+            self.last_reward_ts_ms(farm_id, reward_token).get(),
+            timestamp_ms,
+        );
+
+        acc_reward_per_share
+            + self.reward_per_sec(farm_id, reward_token).get() * rewarded_ms * BigUint::from(WAD)
+                / (boosted_share_supply * 1000u64)
+    }
+
+    /// Binary searches the latest checkpoint that started at or before `timestamp_ms`,
+    /// keeping reward calculation gas logarithmic in the number of rate changes.
+    fn find_rate_checkpoint(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+    ) -> Option<RateCheckpoint<Self::Api>> {
+        let history = self.rate_history(farm_id, reward_token);
+
+        let mut low = 1;
+        let mut high = history.len();
+        let mut found = None;
+        while low <= high {
+            let mid = low + (high - low) / 2;
+            if history.get(mid).start_ts_ms <= timestamp_ms {
+                found = Some(mid);
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        found.map(|index| history.get(index))
+    }
+
+    /// Records the rates that apply from `timestamp_ms` on. Multiple changes within the same
+    /// timestamp overwrite each other, since no rewards accrue between them.
+    fn push_rate_checkpoint(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+        reward_per_sec: &BigUint,
+        reward_per_share: &BigUint,
+    ) {
+        let checkpoint = RateCheckpoint {
+            start_ts_ms: timestamp_ms,
+            reward_per_sec: reward_per_sec.clone(),
+            reward_per_share: reward_per_share.clone(),
+            acc_reward_per_share: self.get_acc_reward_per_share(
+                farm_id,
+                reward_token,
+                timestamp_ms,
+            ),
+        };
+
+        let mut history = self.rate_history(farm_id, reward_token);
+        let len = history.len();
+        if len > 0 && history.get(len).start_ts_ms == timestamp_ms {
+            history.set(len, &checkpoint);
+        } else {
+            history.push(&checkpoint);
+        }
+    }
+
+    /// Must be called before any change of the total share supply or of the rates,
+    /// so that the elapsed period is distributed with the values that applied to it.
+    fn update_acc_reward_per_share(&self, farm_id: FarmId, timestamp_ms: u64) {
+        let emission_mode = self.emission_mode(farm_id).get();
+
+        for reward_token in self.reward_tokens(farm_id).iter() {
+            if self.last_reward_ts_ms(farm_id, &reward_token).get() >= timestamp_ms {
+                continue;
+            }
+
+            self.accrued_rewards(farm_id, &reward_token)
+                .set(self.get_accrued_rewards(farm_id, &reward_token, timestamp_ms));
+            if emission_mode == EmissionMode::Pool {
+                self.acc_reward_per_share(farm_id, &reward_token)
+                    .set(self.get_pool_acc_reward_per_share(farm_id, &reward_token, timestamp_ms));
+            }
+            self.last_reward_ts_ms(farm_id, &reward_token)
+                .set(timestamp_ms);
+        }
+    }
+
+    /// Rewards accrued to the boosted share supply up to `timestamp_ms` and not paid out yet.
+    /// Computed on the aggregate supply, so it may exceed the sum of the rounded down payouts.
+    fn get_accrued_rewards(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+    ) -> BigUint {
+        let accrued_rewards = self.accrued_rewards(farm_id, reward_token).get();
+
+        let last_reward_ts_ms = self.last_reward_ts_ms(farm_id, reward_token).get();
+        if timestamp_ms <= last_reward_ts_ms {
+            return accrued_rewards;
+        }
+
+        let last_acc_reward_per_share = match self.emission_mode(farm_id).get() {
+            EmissionMode::FixedRate => {
+                self.get_fixed_rate_acc_reward_per_share(farm_id, reward_token, last_reward_ts_ms)
+            }
+            EmissionMode::Pool => self.acc_reward_per_share(farm_id, reward_token).get(),
+        };
+        let acc_reward_per_share =
+            self.get_acc_reward_per_share(farm_id, reward_token, timestamp_ms);
+
+        accrued_rewards
+            + (acc_reward_per_share - last_acc_reward_per_share)
+                * self.boosted_share_supply(farm_id).get()
+                / BigUint::from(WAD)
+    }
+
+    /// Takes paid out rewards off the accrued ones, flooring at zero to absorb rounding.
+    fn settle_accrued_rewards(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        self.accrued_rewards(farm_id, reward_token)
+            .update(|accrued| {
+                if *accrued > *amount {
+                    *accrued -= amount;
+                } else {
+                    *accrued = BigUint::zero();
+                }
+            });
+    }
+
+    /// Returns the rewards emitted per second as a `(numerator, divisor)` pair, at the current
+    /// boosted share supply for `FixedRate` farms.
+    fn get_emission_rate(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> (BigUint, BigUint) {
+        let reward_per_sec = self.reward_per_sec(farm_id, reward_token).get();
+        match self.emission_mode(farm_id).get() {
+            EmissionMode::FixedRate => (
+                reward_per_sec * self.boosted_share_supply(farm_id).get(),
+                self.reward_per_share(farm_id, reward_token).get(),
+            ),
+            EmissionMode::Pool => (reward_per_sec, BigUint::from(1u64)),
+        }
+    }
+
+    fn get_projected_obligations(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+    ) -> BigUint {
+        let remaining_ms = self.get_rewarded_ms(farm_id, reward_token, timestamp_ms, u64::MAX);
+        let (emission_per_sec, divisor) = self.get_emission_rate(farm_id, reward_token);
+
+        self.get_accrued_rewards(farm_id, reward_token, timestamp_ms)
+            + emission_per_sec * remaining_ms / (divisor * 1000u64)
+    }
+
+    fn require_reserve_covers_obligations(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        timestamp_ms: u64,
+    ) {
+        if !self.solvency_check_enabled(farm_id).get() {
+            return;
+        }
+
+        require!(
+            self.rewards_reserve(farm_id, reward_token).get()
+                >= self.get_projected_obligations(farm_id, reward_token, timestamp_ms),
+            ERROR_RESERVE_BELOW_OBLIGATIONS
+        );
+    }
+
+    fn increase_staked_supply(&self, farm_id: FarmId, address: &ManagedAddress, amount: &BigUint) {
+        self.total_staked(farm_id).update(|total| *total += amount);
+        self.total_share_supply(farm_id)
+            .update(|supply| *supply += amount);
+        self.user_staked(farm_id, address)
+            .update(|staked| *staked += amount);
+    }
+
+    /// Share tokens are transferable, so an address may unstake more than it has staked itself;
+    /// its own total is floored at zero in that case.
+    fn decrease_staked_supply(&self, farm_id: FarmId, address: &ManagedAddress, amount: &BigUint) {
+        self.total_staked(farm_id).update(|total| *total -= amount);
+        self.total_share_supply(farm_id)
+            .update(|supply| *supply -= amount);
+        self.user_staked(farm_id, address).update(|staked| {
+            if *staked > *amount {
+                *staked -= amount;
+            } else {
+                *staked = BigUint::zero();
+            }
+        });
+    }
+
     // === Storage ===
 
+    #[view(getLastFarmId)]
+    #[storage_mapper("last_farm_id")]
+    fn last_farm_id(&self) -> SingleValueMapper<FarmId>;
+
+    #[storage_mapper("farm_ids")]
+    fn farm_ids(&self) -> UnorderedSetMapper<FarmId>;
+
+    #[view(getFarmIdByShareToken)]
+    #[storage_mapper("share_token_farm_id")]
+    fn share_token_farm_id(&self, share_token: &TokenIdentifier) -> SingleValueMapper<FarmId>;
+
     #[storage_mapper("staked_token")]
-    fn staked_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+    fn staked_token(&self, farm_id: FarmId) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
 
-    #[storage_mapper("reward_token")]
-    fn reward_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+    #[view(getRewardTokens)]
+    #[storage_mapper("reward_tokens")]
+    fn reward_tokens(&self, farm_id: FarmId) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
 
     #[storage_mapper("start_ts_ms")]
-    fn start_ts_ms(&self) -> SingleValueMapper<u64>;
+    fn start_ts_ms(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<u64>;
 
     #[storage_mapper("end_ts_ms")]
-    fn end_ts_ms(&self) -> SingleValueMapper<u64>;
+    fn end_ts_ms(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<u64>;
 
     #[storage_mapper("reward_per_sec")]
-    fn reward_per_sec(&self) -> SingleValueMapper<BigUint>;
+    fn reward_per_sec(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
 
     #[storage_mapper("reward_per_share")]
-    fn reward_per_share(&self) -> SingleValueMapper<BigUint>;
+    fn reward_per_share(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
 
     #[storage_mapper("rewards_reserve")]
-    fn rewards_reserve(&self) -> SingleValueMapper<BigUint>;
+    fn rewards_reserve(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
 
     #[storage_mapper("share_token")]
-    fn share_token(&self) -> NonFungibleTokenMapper;
+    fn share_token(&self, farm_id: FarmId) -> NonFungibleTokenMapper;
+
+    #[storage_mapper("emission_mode")]
+    fn emission_mode(&self, farm_id: FarmId) -> SingleValueMapper<EmissionMode>;
+
+    #[storage_mapper("acc_reward_per_share")]
+    fn acc_reward_per_share(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("rate_history")]
+    fn rate_history(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> VecMapper<RateCheckpoint<Self::Api>>;
+
+    #[storage_mapper("last_reward_ts_ms")]
+    fn last_reward_ts_ms(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<u64>;
+
+    #[view(getTotalStaked)]
+    #[storage_mapper("total_staked")]
+    fn total_staked(&self, farm_id: FarmId) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalShareSupply)]
+    #[storage_mapper("total_share_supply")]
+    fn total_share_supply(&self, farm_id: FarmId) -> SingleValueMapper<BigUint>;
+
+    /// Share supply weighted by the lock multipliers, which `Pool` farms distribute over.
+    #[view(getBoostedShareSupply)]
+    #[storage_mapper("boosted_share_supply")]
+    fn boosted_share_supply(&self, farm_id: FarmId) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("accrued_rewards")]
+    fn accrued_rewards(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("treasury")]
+    fn treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getWithdrawalDestinations)]
+    #[storage_mapper("withdrawal_destinations")]
+    fn withdrawal_destinations(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(isSolvencyCheckEnabled)]
+    #[storage_mapper("solvency_check_enabled")]
+    fn solvency_check_enabled(&self, farm_id: FarmId) -> SingleValueMapper<bool>;
+
+    #[view(getUserStaked)]
+    #[storage_mapper("user_staked")]
+    fn user_staked(&self, farm_id: FarmId, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("early_unstake_penalty")]
+    fn early_unstake_penalty(
+        &self,
+        farm_id: FarmId,
+    ) -> SingleValueMapper<EarlyUnstakePenalty<Self::Api>>;
+
+    #[view(getRewardFee)]
+    #[storage_mapper("reward_fee_bps")]
+    fn reward_fee_bps(&self) -> SingleValueMapper<u64>;
+
+    #[view(getStakeFee)]
+    #[storage_mapper("stake_fee_bps")]
+    fn stake_fee_bps(&self) -> SingleValueMapper<u64>;
 
     // === Events ===
 
     #[event("farmCreated")]
     fn event_farm_created(
         &self,
+        #[indexed] farm_id: FarmId,
         #[indexed] staked_token: &EgldOrEsdtTokenIdentifier,
         #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
         #[indexed] start_ts_ms: u64,
         #[indexed] end_ts_ms: u64,
         #[indexed] reward_per_sec: &BigUint,
         #[indexed] reward_per_share: &BigUint,
+        #[indexed] emission_mode: EmissionMode,
         #[indexed] share_token: &TokenIdentifier,
     );
 
+    #[event("rewardTokenAdded")]
+    fn event_reward_token_added(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] start_ts_ms: u64,
+        #[indexed] end_ts_ms: u64,
+        #[indexed] reward_per_sec: &BigUint,
+        #[indexed] reward_per_share: &BigUint,
+    );
+
     #[event("startTsModified")]
-    fn event_start_ts_modified(&self, #[indexed] new_start_ts_ms: u64);
+    fn event_start_ts_modified(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] new_start_ts_ms: u64,
+    );
 
     #[event("endTsModified")]
-    fn event_end_ts_modified(&self, #[indexed] new_end_ts_ms: u64);
+    fn event_end_ts_modified(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] new_end_ts_ms: u64,
+    );
 
     #[event("rewardsModified")]
     fn event_rewards_modified(
         &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
         #[indexed] new_reward_per_sec: &BigUint,
         #[indexed] new_reward_per_share: &BigUint,
     );
 
     #[event("rewardsDeposited")]
-    fn event_rewards_deposited(&self, #[indexed] amount: &BigUint);
+    fn event_rewards_deposited(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
 
     #[event("rewardsWithdrawn")]
-    fn event_rewards_withdrawn(&self, #[indexed] amount: &BigUint);
+    fn event_rewards_withdrawn(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+        #[indexed] to: &ManagedAddress,
+    );
+
+    #[event("solvencyCheckSet")]
+    fn event_solvency_check_set(&self, #[indexed] farm_id: FarmId, #[indexed] enabled: bool);
+
+    #[event("treasurySet")]
+    fn event_treasury_set(&self, #[indexed] treasury: &ManagedAddress);
+
+    #[event("withdrawalDestinationsAdded")]
+    fn event_withdrawal_destinations_added(
+        &self,
+        #[indexed] addresses: &MultiValueEncoded<ManagedAddress>,
+    );
+
+    #[event("withdrawalDestinationsRemoved")]
+    fn event_withdrawal_destinations_removed(
+        &self,
+        #[indexed] addresses: &MultiValueEncoded<ManagedAddress>,
+    );
+
+    #[event("earlyUnstakePenaltySet")]
+    fn event_early_unstake_penalty_set(
+        &self,
+        #[indexed] farm_id: FarmId,
+        penalty: &EarlyUnstakePenalty<Self::Api>,
+    );
+
+    #[event("protocolFeesSet")]
+    fn event_protocol_fees_set(
+        &self,
+        #[indexed] reward_fee_bps: u64,
+        #[indexed] stake_fee_bps: u64,
+    );
 
     // === Callbacks ===
 
     #[callback]
     fn share_token_issuance_callback(
         &self,
+        farm_id: FarmId,
         caller: &ManagedAddress,
         staked_token: &EgldOrEsdtTokenIdentifier,
         reward_token: &EgldOrEsdtTokenIdentifier,
@@ -240,36 +1250,47 @@ pub trait FarmModule: crate::admins::AdminsModule {
         end_ts_ms: u64,
         reward_per_sec: &BigUint,
         reward_per_share: &BigUint,
+        emission_mode: EmissionMode,
 
         #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
     ) {
         match result {
             ManagedAsyncCallResult::Ok(share_token_identifier) => {
-                self.staked_token().set(staked_token);
-                self.reward_token().set(reward_token);
-                self.start_ts_ms().set(start_ts_ms);
-                self.end_ts_ms().set(end_ts_ms);
-                self.reward_per_sec().set(reward_per_sec);
-                self.reward_per_share().set(reward_per_share);
+                self.farm_ids().insert(farm_id);
+                self.staked_token(farm_id).set(staked_token);
+                self.emission_mode(farm_id).set(emission_mode);
+                self.register_reward_token(
+                    farm_id,
+                    reward_token,
+                    start_ts_ms,
+                    end_ts_ms,
+                    reward_per_sec,
+                    reward_per_share,
+                );
 
                 self.event_farm_created(
+                    farm_id,
                     staked_token,
                     reward_token,
                     start_ts_ms,
                     end_ts_ms,
                     reward_per_sec,
                     reward_per_share,
+                    emission_mode,
                     &share_token_identifier,
                 );
 
-                self.share_token().set_token_id(share_token_identifier);
+                self.share_token_farm_id(&share_token_identifier)
+                    .set(farm_id);
+                self.share_token(farm_id)
+                    .set_token_id(share_token_identifier);
             }
             ManagedAsyncCallResult::Err(_) => {
                 let (token, amount) = self.call_value().egld_or_single_fungible_esdt();
                 if token.is_egld() && amount > 0 {
                     self.tx().to(caller).egld(&amount).transfer();
                 }
-                self.share_token().clear();
+                self.share_token(farm_id).clear();
             }
         }
     }
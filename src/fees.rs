@@ -0,0 +1,174 @@
+use constants::{MAX_PERCENTAGE, MAX_PROTOCOL_FEE_BPS};
+use errors::{ERROR_NOTHING_TO_WITHDRAW, ERROR_WRONG_FEE};
+use multiversx_sc::imports::*;
+use structs::{FarmId, Role, TimelockAction};
+
+/// Smart Contract module that charges the protocol fees.
+///
+/// It provides:
+/// * endpoints where the owner sets the fees, through the timelock, and the fee collector
+/// * an endpoint where a treasurer withdraws the accrued fees to the fee collector
+/// * views to read the fee configuration and the accrued fees
+/// * methods to charge a fee on claimed rewards and on staked principal
+///
+/// Fees are kept apart from the rewards reserves, in one balance per token. The stake fee is only
+/// charged on tokens entering the farm: compounded rewards already paid the reward fee, and a
+/// cancelled unbonding stakes back principal that paid the stake fee when first staked.
+#[multiversx_sc::module]
+pub trait FeesModule:
+    crate::admins::AdminsModule
+    + crate::pause::PauseModule
+    + crate::timelock::TimelockModule
+    + crate::farm::FarmModule
+{
+    // === Endpoints ===
+
+    /// Queued as a timelock action while a timelock delay is set.
+    #[endpoint(setProtocolFees)]
+    fn set_protocol_fees(&self, reward_fee_bps: u64, stake_fee_bps: u64) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        require!(
+            reward_fee_bps <= MAX_PROTOCOL_FEE_BPS && stake_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            ERROR_WRONG_FEE
+        );
+
+        self.queue_or_apply(TimelockAction::SetProtocolFees {
+            reward_fee_bps,
+            stake_fee_bps,
+        });
+    }
+
+    #[endpoint(setFeeCollector)]
+    fn set_fee_collector(&self, fee_collector: ManagedAddress) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        self.fee_collector().set(&fee_collector);
+
+        self.event_fee_collector_set(&fee_collector);
+    }
+
+    /// Sends the accrued fees of `tokens`, or of every token if none is given, to the fee collector.
+    #[endpoint(withdrawFees)]
+    fn withdraw_fees(&self, tokens: MultiValueEncoded<EgldOrEsdtTokenIdentifier>) {
+        self.require_has_role(Role::Treasurer, &self.blockchain().get_caller());
+
+        let tokens: ManagedVec<EgldOrEsdtTokenIdentifier> = if tokens.is_empty() {
+            self.accrued_fees().keys().collect()
+        } else {
+            tokens.to_vec()
+        };
+
+        let fee_collector = self.get_fee_collector();
+        let mut payments = ManagedVec::<Self::Api, EgldOrEsdtTokenPayment>::new();
+        for token in tokens.iter() {
+            let amount = self.accrued_fees().remove(&token).unwrap_or_default();
+            if amount == 0 {
+                continue;
+            }
+
+            self.event_fees_withdrawn(&fee_collector, &token, &amount);
+            payments.push(EgldOrEsdtTokenPayment::new(token.clone(), 0, amount));
+        }
+        require!(!payments.is_empty(), ERROR_NOTHING_TO_WITHDRAW);
+
+        self.tx().to(&fee_collector).payment(&payments).transfer();
+    }
+
+    // === Views ===
+
+    /// Destination of `withdrawFees`, the treasury until a fee collector is set.
+    #[view(getFeeCollector)]
+    fn get_fee_collector(&self) -> ManagedAddress {
+        if self.fee_collector().is_empty() {
+            return self.get_treasury();
+        }
+
+        self.fee_collector().get()
+    }
+
+    #[view(getAccruedFees)]
+    fn get_accrued_fees(&self) -> MultiValueEncoded<EgldOrEsdtTokenPayment> {
+        let mut fees = MultiValueEncoded::new();
+        for (token, amount) in self.accrued_fees().iter() {
+            fees.push(EgldOrEsdtTokenPayment::new(token, 0, amount));
+        }
+
+        fees
+    }
+
+    // === Private ===
+
+    /// Moves the reward fee out of `amount` into the fee balance and returns what is left for the recipient.
+    fn charge_reward_fee(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> BigUint {
+        self.charge_fee(farm_id, address, token, amount, self.reward_fee_bps().get())
+    }
+
+    /// Moves the stake fee out of `amount` into the fee balance and returns what is left to stake.
+    fn charge_stake_fee(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> BigUint {
+        self.charge_fee(farm_id, address, token, amount, self.stake_fee_bps().get())
+    }
+
+    fn charge_fee(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        fee_bps: u64,
+    ) -> BigUint {
+        let fee = amount * fee_bps / MAX_PERCENTAGE;
+        if fee == 0 {
+            return amount.clone();
+        }
+
+        let accrued_fee = self.accrued_fees().get(token).unwrap_or_default() + &fee;
+        self.accrued_fees().insert(token.clone(), accrued_fee);
+
+        self.event_protocol_fee_charged(farm_id, address, token, &fee);
+
+        amount - &fee
+    }
+
+    // === Storage ===
+
+    #[storage_mapper("fee_collector")]
+    fn fee_collector(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("accrued_fees")]
+    fn accrued_fees(&self) -> MapMapper<EgldOrEsdtTokenIdentifier, BigUint>;
+
+    // === Events ===
+
+    #[event("feeCollectorSet")]
+    fn event_fee_collector_set(&self, #[indexed] fee_collector: &ManagedAddress);
+
+    #[event("protocolFeeCharged")]
+    fn event_protocol_fee_charged(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("feesWithdrawn")]
+    fn event_fees_withdrawn(
+        &self,
+        #[indexed] fee_collector: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+}
@@ -1,31 +1,66 @@
 #![no_std]
 
-use multiversx_sc::imports::*;
+use constants::LEGACY_STAKED_TOKEN_KEY;
+use errors::ERROR_LEGACY_STORAGE_LAYOUT;
+use multiversx_sc::{imports::*, storage::StorageKey};
 
-mod admins;
-mod farm;
-mod pause;
-mod rewards;
-mod staking;
+pub mod admins;
+pub mod farm;
+pub mod fees;
+pub mod locking;
+pub mod pause;
+pub mod penalty;
+pub mod rewards;
+pub mod staking;
+pub mod timelock;
+pub mod unbonding;
 
 /// A Smart Contract that allows users to stake tokens and earn rewards over time.
 /// - Users can stake a specific token and receive share tokens in return that represent their stake.
 /// - Users can claim rewards based on the time they have staked their tokens and the fixed reward rate.
 /// - Users can unstake their tokens by burning their share tokens.
 /// - During staking or unstaking, any pending rewards are automatically claimed and sent to the user.
+/// - Users can optionally lock their stake for one of the farm's lock tiers to boost their rewards.
+/// - Locked positions can be unstaked early if the farm charges an early unstake penalty.
+/// - Farms can hold unstaked tokens for an unbonding period, after which they are withdrawn.
 /// - Also, if multiple share tokens are sent, they are merged into a single share token to reduce NFT clutter.
+/// - Multiple farms can live in the same deployment, each identified by its farm id.
 /// - Farm Owner is responsible to fund the rewards reserve and set the farm parameters.
+/// - Protocol fees can be charged on claimed rewards and staked principal, withdrawn to a fee collector.
+/// - Rate, end timestamp and reserve withdrawal changes can be held by a timelock the owner can cancel.
 #[multiversx_sc::contract]
 pub trait Template:
     admins::AdminsModule
     + pause::PauseModule
+    + timelock::TimelockModule
     + farm::FarmModule
+    + fees::FeesModule
+    + locking::LockingModule
     + staking::StakingModule
     + rewards::RewardsModule
+    + penalty::PenaltyModule
+    + unbonding::UnbondingModule
 {
     #[init]
-    fn init(&self) {}
+    fn init(&self) {
+        self.owner().set(self.blockchain().get_caller());
+    }
 
+    /// Records the owner of deployments that predate it, so that the contract can later be
+    /// made its own owner on chain without losing track of who owns it.
+    ///
+    /// Deployments of the single-farm layout cannot be upgraded in place: their farm state is
+    /// stored under keys no farm id points to, and the staked supply it never tracked cannot be
+    /// rebuilt on chain. Such deployments are rejected and must be migrated to a new deployment.
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self) {
+        let legacy_staked_token = SingleValueMapper::<Self::Api, EgldOrEsdtTokenIdentifier>::new(
+            StorageKey::new(LEGACY_STAKED_TOKEN_KEY),
+        );
+        require!(legacy_staked_token.is_empty(), ERROR_LEGACY_STORAGE_LAYOUT);
+
+        if self.owner().is_empty() {
+            self.owner().set(self.blockchain().get_owner_address());
+        }
+    }
 }
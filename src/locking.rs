@@ -0,0 +1,139 @@
+use constants::{MAX_LOCK_MULTIPLIER_BPS, MAX_PERCENTAGE};
+use errors::{ERROR_INVALID_LOCK_PERIOD, ERROR_LOCK_TIER_NOT_FOUND, ERROR_WRONG_MULTIPLIER};
+use multiversx_sc::imports::*;
+use structs::{FarmId, LockTier, Role};
+
+/// Smart Contract module that offers lock-up tiers.
+///
+/// It provides:
+/// * two endpoints where a rate manager can set/remove the lock tiers of a farm
+/// * an endpoint where a schedule manager can set the minimum stake duration of a farm
+/// * views to list the lock tiers and the minimum stake duration of a farm
+/// * a method to resolve the lock terms of a new stake
+#[multiversx_sc::module]
+pub trait LockingModule:
+    crate::admins::AdminsModule + crate::timelock::TimelockModule + crate::farm::FarmModule
+{
+    // === Endpoints ===
+
+    #[endpoint(setLockTier)]
+    fn set_lock_tier(&self, farm_id: FarmId, lock_period_ms: u64, multiplier_bps: u64) {
+        self.require_has_role(Role::RateManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        require!(lock_period_ms > 0, ERROR_INVALID_LOCK_PERIOD);
+        require!(
+            (MAX_PERCENTAGE..=MAX_LOCK_MULTIPLIER_BPS).contains(&multiplier_bps),
+            ERROR_WRONG_MULTIPLIER
+        );
+
+        self.lock_tiers(farm_id)
+            .insert(lock_period_ms, multiplier_bps);
+
+        self.event_lock_tier_set(farm_id, lock_period_ms, multiplier_bps);
+    }
+
+    #[endpoint(removeLockTier)]
+    fn remove_lock_tier(&self, farm_id: FarmId, lock_period_ms: u64) {
+        self.require_has_role(Role::RateManager, &self.blockchain().get_caller());
+
+        require!(
+            self.lock_tiers(farm_id).remove(&lock_period_ms).is_some(),
+            ERROR_LOCK_TIER_NOT_FOUND
+        );
+
+        self.event_lock_tier_removed(farm_id, lock_period_ms);
+    }
+
+    /// New stakes stay locked for at least `min_stake_duration_ms`, without any boost; `0` disables it.
+    #[endpoint(setMinStakeDuration)]
+    fn set_min_stake_duration(&self, farm_id: FarmId, min_stake_duration_ms: u64) {
+        self.require_has_role(Role::ScheduleManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        self.min_stake_duration_ms(farm_id)
+            .set(min_stake_duration_ms);
+
+        self.event_min_stake_duration_set(farm_id, min_stake_duration_ms);
+    }
+
+    // === Views ===
+
+    #[view(getLockTiers)]
+    fn get_lock_tiers(&self, farm_id: FarmId) -> MultiValueEncoded<LockTier> {
+        let mut tiers = MultiValueEncoded::new();
+        for (lock_period_ms, multiplier_bps) in self.lock_tiers(farm_id).iter() {
+            tiers.push(LockTier {
+                lock_period_ms,
+                multiplier_bps,
+            });
+        }
+
+        tiers
+    }
+
+    #[view(getMinStakeDuration)]
+    fn get_min_stake_duration(&self, farm_id: FarmId) -> u64 {
+        self.min_stake_duration_ms(farm_id).get()
+    }
+
+    // === Private ===
+
+    /// Returns the `(lock_until_ts_ms, multiplier_bps)` of a stake locked for `opt_lock_period_ms`.
+    fn get_lock_terms(
+        &self,
+        farm_id: FarmId,
+        opt_lock_period_ms: OptionalValue<u64>,
+        current_ts_ms: u64,
+    ) -> (u64, u64) {
+        let min_stake_duration_ms = self.min_stake_duration_ms(farm_id).get();
+        let min_lock_until_ts_ms = if min_stake_duration_ms > 0 {
+            current_ts_ms + min_stake_duration_ms
+        } else {
+            0
+        };
+
+        match opt_lock_period_ms {
+            OptionalValue::Some(lock_period_ms) => {
+                let multiplier_bps = self
+                    .lock_tiers(farm_id)
+                    .get(&lock_period_ms)
+                    .unwrap_or_else(|| sc_panic!(ERROR_LOCK_TIER_NOT_FOUND));
+
+                (
+                    core::cmp::max(current_ts_ms + lock_period_ms, min_lock_until_ts_ms),
+                    multiplier_bps,
+                )
+            }
+            OptionalValue::None => (min_lock_until_ts_ms, MAX_PERCENTAGE),
+        }
+    }
+
+    // === Storage ===
+
+    #[storage_mapper("lock_tiers")]
+    fn lock_tiers(&self, farm_id: FarmId) -> MapMapper<u64, u64>;
+
+    #[storage_mapper("min_stake_duration_ms")]
+    fn min_stake_duration_ms(&self, farm_id: FarmId) -> SingleValueMapper<u64>;
+
+    // === Events ===
+
+    #[event("lockTierSet")]
+    fn event_lock_tier_set(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] lock_period_ms: u64,
+        #[indexed] multiplier_bps: u64,
+    );
+
+    #[event("lockTierRemoved")]
+    fn event_lock_tier_removed(&self, #[indexed] farm_id: FarmId, #[indexed] lock_period_ms: u64);
+
+    #[event("minStakeDurationSet")]
+    fn event_min_stake_duration_set(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] min_stake_duration_ms: u64,
+    );
+}
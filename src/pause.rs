@@ -1,19 +1,21 @@
-use errors::{ERROR_NOT_PAUSED, ERROR_PAUSED};
+use errors::{ERROR_EMERGENCY_MODE, ERROR_NOT_EMERGENCY_MODE, ERROR_NOT_PAUSED, ERROR_PAUSED};
 use multiversx_sc::imports::*;
+use structs::Role;
 
 /// Smart Contract module that offers pausability.
 ///
 /// It provides:
-/// * two endpoints where an admin can pause/unpause the contract
-/// * a view to check if the contract is paused
-/// * two methods to require the contract to be paused/not paused
+/// * two endpoints where a pauser can pause/unpause the contract
+/// * two endpoints where a pauser can enable/disable the emergency mode
+/// * views to check if the contract is paused or in emergency mode
+/// * methods to require the contract to be paused/not paused
 #[multiversx_sc::module]
 pub trait PauseModule: crate::admins::AdminsModule {
     // === Endpoints ===
 
     #[endpoint(pause)]
     fn pause(&self) {
-        self.require_is_admin(&self.blockchain().get_caller());
+        self.require_has_role(Role::Pauser, &self.blockchain().get_caller());
         self.require_not_paused();
 
         self.is_paused().set(true);
@@ -22,13 +24,32 @@ pub trait PauseModule: crate::admins::AdminsModule {
 
     #[endpoint(unpause)]
     fn unpause(&self) {
-        self.require_is_admin(&self.blockchain().get_caller());
+        self.require_has_role(Role::Pauser, &self.blockchain().get_caller());
         self.require_paused();
 
         self.is_paused().set(false);
         self.event_unpaused();
     }
 
+    /// Lets users exit through `emergencyUnstake` while the contract is paused.
+    #[endpoint(enableEmergencyMode)]
+    fn enable_emergency_mode(&self) {
+        self.require_has_role(Role::Pauser, &self.blockchain().get_caller());
+        require!(!self.emergency_mode().get(), ERROR_EMERGENCY_MODE);
+
+        self.emergency_mode().set(true);
+        self.event_emergency_mode_enabled();
+    }
+
+    #[endpoint(disableEmergencyMode)]
+    fn disable_emergency_mode(&self) {
+        self.require_has_role(Role::Pauser, &self.blockchain().get_caller());
+        require!(self.emergency_mode().get(), ERROR_NOT_EMERGENCY_MODE);
+
+        self.emergency_mode().set(false);
+        self.event_emergency_mode_disabled();
+    }
+
     // === Private ===
 
     fn require_paused(&self) {
@@ -39,12 +60,23 @@ pub trait PauseModule: crate::admins::AdminsModule {
         require!(!self.is_paused().get(), ERROR_PAUSED);
     }
 
+    fn require_not_paused_or_emergency(&self) {
+        require!(
+            !self.is_paused().get() || self.emergency_mode().get(),
+            ERROR_PAUSED
+        );
+    }
+
     // === Storage ===
 
     #[view(isPaused)]
     #[storage_mapper("is_paused")]
     fn is_paused(&self) -> SingleValueMapper<bool>;
 
+    #[view(isEmergencyMode)]
+    #[storage_mapper("emergency_mode")]
+    fn emergency_mode(&self) -> SingleValueMapper<bool>;
+
     // === Events ===
 
     #[event("paused")]
@@ -52,4 +84,10 @@ pub trait PauseModule: crate::admins::AdminsModule {
 
     #[event("unpaused")]
     fn event_unpaused(&self);
+
+    #[event("emergencyModeEnabled")]
+    fn event_emergency_mode_enabled(&self);
+
+    #[event("emergencyModeDisabled")]
+    fn event_emergency_mode_disabled(&self);
 }
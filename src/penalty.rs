@@ -0,0 +1,267 @@
+use constants::{MAX_EARLY_UNSTAKE_PENALTY_BPS, MAX_PERCENTAGE};
+use errors::{
+    ERROR_CANNOT_BURN_EGLD, ERROR_MISSING_BURN_ROLE, ERROR_PENALTY_NOT_SET,
+    ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN, ERROR_WRONG_PENALTY,
+};
+use multiversx_sc::imports::*;
+use structs::{
+    EarlyUnstakePenalty, FarmId, PenaltyDestination, PenaltySource, Role, TimelockAction,
+};
+
+/// Smart Contract module that charges a penalty on early unstakes.
+///
+/// It provides:
+/// * two endpoints where a rate manager can set/remove the early unstake penalty of a farm; setting
+///   one goes through the timelock, as it also applies to positions already locked
+/// * views to read the penalty configuration and preview the penalty of a share token
+/// * methods to compute and route the penalty of an unstake
+///
+/// Positions still locked can only be unstaked when the farm has a penalty set.
+#[multiversx_sc::module]
+pub trait PenaltyModule:
+    crate::admins::AdminsModule
+    + crate::pause::PauseModule
+    + crate::timelock::TimelockModule
+    + crate::farm::FarmModule
+    + crate::fees::FeesModule
+    + crate::rewards::RewardsModule
+{
+    // === Endpoints ===
+
+    #[endpoint(setEarlyUnstakePenalty)]
+    fn set_early_unstake_penalty(
+        &self,
+        farm_id: FarmId,
+        penalty_bps: u64,
+        source: PenaltySource,
+        destination: PenaltyDestination<Self::Api>,
+    ) {
+        self.require_has_role(Role::RateManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        require!(
+            penalty_bps > 0 && penalty_bps <= MAX_EARLY_UNSTAKE_PENALTY_BPS,
+            ERROR_WRONG_PENALTY
+        );
+
+        if source == PenaltySource::Principal {
+            let staked_token = self.staked_token(farm_id).get();
+            match &destination {
+                PenaltyDestination::Burn => {
+                    require!(staked_token.is_esdt(), ERROR_CANNOT_BURN_EGLD);
+                    require!(
+                        self.blockchain()
+                            .get_esdt_local_roles(&staked_token.unwrap_esdt())
+                            .has_role(&EsdtLocalRole::Burn),
+                        ERROR_MISSING_BURN_ROLE
+                    );
+                }
+                PenaltyDestination::Redistribute => require!(
+                    self.reward_tokens(farm_id).contains(&staked_token),
+                    ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN
+                ),
+                PenaltyDestination::Address(_) => {}
+            }
+        }
+
+        self.queue_or_apply(TimelockAction::SetEarlyUnstakePenalty {
+            farm_id,
+            penalty: EarlyUnstakePenalty {
+                penalty_bps,
+                source,
+                destination,
+            },
+        });
+    }
+
+    #[endpoint(removeEarlyUnstakePenalty)]
+    fn remove_early_unstake_penalty(&self, farm_id: FarmId) {
+        self.require_has_role(Role::RateManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        require!(
+            !self.early_unstake_penalty(farm_id).is_empty(),
+            ERROR_PENALTY_NOT_SET
+        );
+        self.early_unstake_penalty(farm_id).clear();
+
+        self.event_early_unstake_penalty_removed(farm_id);
+    }
+
+    // === Views ===
+
+    #[view(getEarlyUnstakePenaltyConfig)]
+    fn get_early_unstake_penalty_config(
+        &self,
+        farm_id: FarmId,
+    ) -> OptionalValue<EarlyUnstakePenalty<Self::Api>> {
+        if self.early_unstake_penalty(farm_id).is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(self.early_unstake_penalty(farm_id).get())
+    }
+
+    /// Penalty charged if `amount` of the share token `nonce` held by `address` was unstaked now,
+    /// a rewards penalty being capped by what the reserve holds once the rewards are paid.
+    #[view(getEarlyUnstakePenalty)]
+    fn get_early_unstake_penalty(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+        nonce: u64,
+        amount: BigUint,
+    ) -> MultiValueEncoded<EgldOrEsdtTokenPayment> {
+        let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
+
+        self.require_farm_exists(farm_id);
+
+        let share_token_merged_data =
+            self.calculate_reward(farm_id, address, nonce, &amount, current_timestamp_ms);
+        if share_token_merged_data.lock_until_ts_ms <= current_timestamp_ms
+            || self.early_unstake_penalty(farm_id).is_empty()
+        {
+            return MultiValueEncoded::new();
+        }
+
+        let penalty = self.early_unstake_penalty(farm_id).get();
+        let penalties = self.compute_early_unstake_penalty(
+            farm_id,
+            &penalty,
+            &amount,
+            &share_token_merged_data.rewards,
+        );
+        if penalty.source == PenaltySource::Principal {
+            return penalties.into();
+        }
+
+        // the rewards are paid first, so a short reserve leaves less of the penalty to take
+        let mut taken_penalties = MultiValueEncoded::new();
+        for (index, penalty_payment) in penalties.iter().enumerate() {
+            let reward = share_token_merged_data.rewards.get(index);
+            let reserve = self
+                .rewards_reserve(farm_id, &penalty_payment.token_identifier)
+                .get();
+            let paid_reward = &reward.amount - &penalty_payment.amount;
+            let reserve_left = if reserve > paid_reward {
+                reserve - paid_reward
+            } else {
+                BigUint::zero()
+            };
+
+            taken_penalties.push(EgldOrEsdtTokenPayment::new(
+                penalty_payment.token_identifier.clone(),
+                0,
+                core::cmp::min(reserve_left, penalty_payment.amount.clone()),
+            ));
+        }
+
+        taken_penalties
+    }
+
+    // === Private ===
+
+    /// Returns the penalty payments, in the staked token for `Principal` or in the reward tokens for `Rewards`.
+    fn compute_early_unstake_penalty(
+        &self,
+        farm_id: FarmId,
+        penalty: &EarlyUnstakePenalty<Self::Api>,
+        unstake_amount: &BigUint,
+        rewards: &ManagedVec<EgldOrEsdtTokenPayment>,
+    ) -> ManagedVec<EgldOrEsdtTokenPayment> {
+        let mut penalties = ManagedVec::new();
+        match penalty.source {
+            PenaltySource::Principal => {
+                penalties.push(EgldOrEsdtTokenPayment::new(
+                    self.staked_token(farm_id).get(),
+                    0,
+                    unstake_amount * penalty.penalty_bps / MAX_PERCENTAGE,
+                ));
+            }
+            PenaltySource::Rewards => {
+                for reward in rewards.iter() {
+                    penalties.push(EgldOrEsdtTokenPayment::new(
+                        reward.token_identifier.clone(),
+                        0,
+                        &reward.amount * penalty.penalty_bps / MAX_PERCENTAGE,
+                    ));
+                }
+            }
+        }
+
+        penalties
+    }
+
+    /// Takes reward penalties out of the reserves as far as they hold once the rewards are paid;
+    /// the rest is forfeited. Returns the penalties actually taken.
+    fn take_reward_penalties(
+        &self,
+        farm_id: FarmId,
+        penalties: &ManagedVec<EgldOrEsdtTokenPayment>,
+    ) -> ManagedVec<EgldOrEsdtTokenPayment> {
+        let mut taken_penalties = ManagedVec::new();
+        for penalty in penalties.iter() {
+            let taken = self.take_from_reserve(farm_id, &penalty.token_identifier, &penalty.amount);
+            if taken < penalty.amount {
+                self.settle_accrued_rewards(
+                    farm_id,
+                    &penalty.token_identifier,
+                    &(&penalty.amount - &taken),
+                );
+            }
+
+            taken_penalties.push(EgldOrEsdtTokenPayment::new(
+                penalty.token_identifier.clone(),
+                0,
+                taken,
+            ));
+        }
+
+        taken_penalties
+    }
+
+    fn route_early_unstake_penalty(
+        &self,
+        farm_id: FarmId,
+        destination: &PenaltyDestination<Self::Api>,
+        penalties: &ManagedVec<EgldOrEsdtTokenPayment>,
+    ) {
+        for penalty in penalties.iter() {
+            if penalty.amount == 0 {
+                continue;
+            }
+
+            match destination {
+                PenaltyDestination::Address(address) => {
+                    self.send()
+                        .direct(address, &penalty.token_identifier, 0, &penalty.amount);
+                }
+                PenaltyDestination::Burn => {
+                    require!(penalty.token_identifier.is_esdt(), ERROR_CANNOT_BURN_EGLD);
+                    self.send().esdt_local_burn(
+                        &penalty.token_identifier.clone().unwrap_esdt(),
+                        0,
+                        &penalty.amount,
+                    );
+                }
+                PenaltyDestination::Redistribute => {
+                    self.rewards_reserve(farm_id, &penalty.token_identifier)
+                        .update(|reserve| *reserve += &penalty.amount);
+                }
+            }
+        }
+    }
+
+    // === Events ===
+
+    #[event("earlyUnstakePenaltyRemoved")]
+    fn event_early_unstake_penalty_removed(&self, #[indexed] farm_id: FarmId);
+
+    #[event("earlyUnstakePenaltyCharged")]
+    fn event_early_unstake_penalty_charged(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] penalties: &ManagedVec<EgldOrEsdtTokenPayment>,
+    );
+}
@@ -1,50 +1,202 @@
-use errors::{ERROR_INVALID_SHARE_TOKEN, ERROR_NOT_ENOUGH_REWARDS, ERROR_NO_REWARDS_APPLICABLE};
+use constants::{MAX_PERCENTAGE, WAD};
+use errors::{
+    ERROR_INVALID_SHARE_TOKEN, ERROR_NOT_ENOUGH_REWARDS, ERROR_NO_EXPIRED_BOOST,
+    ERROR_NO_REWARDS_APPLICABLE, ERROR_NO_UNPAID_REWARDS, ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN,
+};
 use multiversx_sc::imports::*;
 use structs::{
-    ShareToken, ShareTokenAttributes, ShareTokenMergedData, ShareTokenMergedDataWithBurns,
-    ShareTokenType,
+    EmissionMode, FarmId, ShareToken, ShareTokenAttributes, ShareTokenBoost, ShareTokenMergedData,
+    ShareTokenMergedDataWithBurns, ShareTokenType,
 };
 
 #[multiversx_sc::module]
 pub trait RewardsModule:
-    crate::admins::AdminsModule + crate::pause::PauseModule + crate::farm::FarmModule
+    crate::admins::AdminsModule
+    + crate::pause::PauseModule
+    + crate::timelock::TimelockModule
+    + crate::farm::FarmModule
+    + crate::fees::FeesModule
 {
     // === Endpoints ===
 
     #[payable]
     #[endpoint(claimRewards)]
-    fn claim_rewards_endpoint(&self) {
+    fn claim_rewards_endpoint(&self, opt_compound: OptionalValue<bool>) {
         self.require_not_paused();
 
         let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
         let caller = self.blockchain().get_caller();
         let transfers = self.call_value().all_esdt_transfers();
+        let farm_id = self.get_farm_id_by_share_tokens(&transfers);
 
-        let token_merged_data = self.claim_rewards(&caller, current_timestamp_ms, &transfers);
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
 
-        self.share_token().nft_create_and_send(
-            &caller,
-            token_merged_data.token_supply.clone(),
-            &self.attributes_to_buffer(current_timestamp_ms),
+        if opt_compound.into_option().unwrap_or(false) {
+            self.compound_rewards(farm_id, &caller, current_timestamp_ms, &transfers);
+            return;
+        }
+
+        self.claim_and_reissue(farm_id, &caller, current_timestamp_ms, &transfers);
+    }
+
+    /// Claims the rewards of the caller's share tokens into `recipient`; the share tokens go back to the caller.
+    #[payable]
+    #[endpoint(claimRewardsTo)]
+    fn claim_rewards_to(&self, recipient: ManagedAddress) {
+        self.require_not_paused();
+
+        let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
+        let transfers = self.call_value().all_esdt_transfers();
+        let farm_id = self.get_farm_id_by_share_tokens(&transfers);
+
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
+
+        self.claim_and_reissue(farm_id, &recipient, current_timestamp_ms, &transfers);
+    }
+
+    #[payable]
+    #[endpoint(compound)]
+    fn compound(&self) {
+        self.require_not_paused();
+
+        let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
+        let caller = self.blockchain().get_caller();
+        let transfers = self.call_value().all_esdt_transfers();
+        let farm_id = self.get_farm_id_by_share_tokens(&transfers);
+
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
+
+        self.compound_rewards(farm_id, &caller, current_timestamp_ms, &transfers);
+    }
+
+    /// Pays the caller's rewards left unpaid by a short reserve, as far as the reserve now allows.
+    #[endpoint(claimUnpaidRewards)]
+    fn claim_unpaid_rewards(&self, farm_id: FarmId) {
+        self.require_not_paused();
+        self.require_farm_exists(farm_id);
+
+        let caller = self.blockchain().get_caller();
+        let mut unpaid_rewards = self.unpaid_rewards(farm_id, &caller);
+        require!(!unpaid_rewards.is_empty(), ERROR_NO_UNPAID_REWARDS);
+
+        let mut payments = ManagedVec::<Self::Api, EgldOrEsdtTokenPayment>::new();
+        for (reward_token, unpaid_amount) in unpaid_rewards.iter() {
+            let amount = self.take_from_reserve(farm_id, &reward_token, &unpaid_amount);
+            if amount > 0 {
+                payments.push(EgldOrEsdtTokenPayment::new(reward_token, 0, amount));
+            }
+        }
+        require!(!payments.is_empty(), ERROR_NOT_ENOUGH_REWARDS);
+
+        let mut net_payments = ManagedVec::<Self::Api, EgldOrEsdtTokenPayment>::new();
+        for payment in payments.iter() {
+            let unpaid_amount = unpaid_rewards.get(&payment.token_identifier).unwrap();
+            if unpaid_amount == payment.amount {
+                unpaid_rewards.remove(&payment.token_identifier);
+            } else {
+                unpaid_rewards.insert(
+                    payment.token_identifier.clone(),
+                    unpaid_amount - &payment.amount,
+                );
+            }
+
+            let net_amount = self.charge_reward_fee(
+                farm_id,
+                &caller,
+                &payment.token_identifier,
+                &payment.amount,
+            );
+            self.event_unpaid_rewards_claimed(
+                farm_id,
+                &caller,
+                &payment.token_identifier,
+                &net_amount,
+            );
+            net_payments.push(EgldOrEsdtTokenPayment::new(
+                payment.token_identifier.clone(),
+                0,
+                net_amount,
+            ));
+        }
+
+        self.send_rewards(&caller, &net_payments);
+    }
+
+    /// Takes the boost of a `Pool` share token nonce off the boosted share supply once its lock has
+    /// ended, so that it stops diluting the other stakers. Anyone can call it.
+    #[endpoint(settleExpiredBoost)]
+    fn settle_expired_boost_endpoint(&self, farm_id: FarmId, nonce: u64) {
+        self.require_not_paused();
+        self.require_farm_exists(farm_id);
+
+        let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
+        let share_token_boost = self.share_token_boost(farm_id, nonce);
+        require!(
+            !share_token_boost.is_empty()
+                && share_token_boost.get().lock_until_ts_ms <= current_timestamp_ms,
+            ERROR_NO_EXPIRED_BOOST
         );
+        let multiplier_bps = share_token_boost.take().multiplier_bps;
+
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
+
+        let mut acc_reward_per_share = ManagedVec::new();
+        for reward_token in self.reward_tokens(farm_id).iter() {
+            acc_reward_per_share.push(self.acc_reward_per_share(farm_id, &reward_token).get());
+        }
+        self.expired_boost_acc_reward_per_share(farm_id, nonce)
+            .set(acc_reward_per_share);
+
+        let expired_boost = self.share_token_nonce_supply(farm_id, nonce).get()
+            * (multiplier_bps - MAX_PERCENTAGE)
+            / MAX_PERCENTAGE;
+        self.boosted_share_supply(farm_id)
+            .update(|supply| *supply -= &expired_boost);
+
+        self.event_boost_settled(farm_id, nonce, &expired_boost);
     }
 
     // === Views ===
 
+    /// Rewards of the share tokens net of the reward fee; a short reserve may pay less.
     #[view(getClaimableRewards)]
     fn get_claimable_rewards(
         &self,
+        farm_id: FarmId,
         address: &ManagedAddress,
         share_tokens: MultiValueEncoded<ShareTokenType<Self::Api>>,
-    ) -> BigUint {
+    ) -> MultiValueEncoded<EgldOrEsdtTokenPayment> {
         let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
 
-        let mut rewards = BigUint::zero();
+        self.require_farm_exists(farm_id);
+
+        let mut rewards = self.empty_rewards(farm_id);
         for share_token in share_tokens.into_iter() {
             let (nonce, amount) = share_token.into_tuple();
-            rewards += self
-                .calculate_reward(address, nonce, &amount, current_timestamp_ms)
-                .reward_amount;
+            let share_token_merged_data =
+                self.calculate_reward(farm_id, address, nonce, &amount, current_timestamp_ms);
+            self.add_rewards(&mut rewards, &share_token_merged_data.rewards);
+        }
+
+        let reward_fee_bps = self.reward_fee_bps().get();
+        let mut net_rewards = MultiValueEncoded::new();
+        for mut reward in rewards.into_iter() {
+            reward.amount -= &reward.amount * reward_fee_bps / MAX_PERCENTAGE;
+            net_rewards.push(reward);
+        }
+
+        net_rewards
+    }
+
+    #[view(getUnpaidRewards)]
+    fn get_unpaid_rewards(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+    ) -> MultiValueEncoded<EgldOrEsdtTokenPayment> {
+        let mut rewards = MultiValueEncoded::new();
+        for (reward_token, amount) in self.unpaid_rewards(farm_id, address).iter() {
+            rewards.push(EgldOrEsdtTokenPayment::new(reward_token, 0, amount));
         }
 
         rewards
@@ -52,59 +204,388 @@ pub trait RewardsModule:
 
     // === Private ===
 
+    fn claim_and_reissue(
+        &self,
+        farm_id: FarmId,
+        recipient: &ManagedAddress,
+        current_timestamp_ms: u64,
+        transfers: &ManagedVec<EsdtTokenPayment>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let token_merged_data = self.claim_rewards(
+            farm_id,
+            &caller,
+            recipient,
+            current_timestamp_ms,
+            transfers,
+            true,
+        );
+
+        self.reissue_share_token(
+            farm_id,
+            &caller,
+            token_merged_data.kept_nonce,
+            &token_merged_data.token_supply,
+            token_merged_data.lock_until_ts_ms,
+            token_merged_data.multiplier_bps,
+            current_timestamp_ms,
+        );
+    }
+
     fn claim_rewards(
         &self,
+        farm_id: FarmId,
         caller: &ManagedAddress,
+        recipient: &ManagedAddress,
         current_timestamp_ms: u64,
         transfers: &ManagedVec<EsdtTokenPayment>,
+        require_rewards: bool,
     ) -> ShareTokenMergedDataWithBurns<Self::Api> {
-        let token_merged_data = self.prepare_rewards_and_burns(transfers, current_timestamp_ms);
-        require!(
-            token_merged_data.reward_amount > BigUint::zero(),
-            ERROR_NO_REWARDS_APPLICABLE
-        );
-        require!(
-            self.rewards_reserve().get() >= token_merged_data.reward_amount,
-            ERROR_NOT_ENOUGH_REWARDS
+        let token_merged_data = self.collect_rewards(
+            farm_id,
+            caller,
+            recipient,
+            current_timestamp_ms,
+            transfers,
+            require_rewards,
         );
 
-        self.rewards_reserve()
-            .update(|current| *current -= &token_merged_data.reward_amount);
+        self.send_rewards(recipient, &token_merged_data.rewards);
+
+        token_merged_data
+    }
+
+    /// Burns the share tokens and takes their rewards out of the reserves, leaving the payout to the caller.
+    ///
+    /// A reserve short of a reward pays what it holds; the remainder is recorded as unpaid to
+    /// `recipient`, so that unstaking never depends on the reserve.
+    ///
+    /// Positions without rewards are accepted unless `require_rewards` is set, as standalone
+    /// claims do.
+    fn collect_rewards(
+        &self,
+        farm_id: FarmId,
+        caller: &ManagedAddress,
+        recipient: &ManagedAddress,
+        current_timestamp_ms: u64,
+        transfers: &ManagedVec<EsdtTokenPayment>,
+        require_rewards: bool,
+    ) -> ShareTokenMergedDataWithBurns<Self::Api> {
+        let token_merged_data =
+            self.prepare_rewards_and_burns(farm_id, transfers, current_timestamp_ms);
+        if require_rewards {
+            require!(
+                token_merged_data
+                    .rewards
+                    .iter()
+                    .any(|reward| reward.amount > 0),
+                ERROR_NO_REWARDS_APPLICABLE
+            );
+        }
+
+        self.pay_collected_rewards(farm_id, caller, recipient, token_merged_data)
+    }
+
+    /// Takes the rewards of prepared share tokens out of the reserves and burns the share tokens.
+    fn pay_collected_rewards(
+        &self,
+        farm_id: FarmId,
+        caller: &ManagedAddress,
+        recipient: &ManagedAddress,
+        mut token_merged_data: ShareTokenMergedDataWithBurns<Self::Api>,
+    ) -> ShareTokenMergedDataWithBurns<Self::Api> {
+        let mut paid_rewards = ManagedVec::new();
+        for reward in token_merged_data.rewards.iter() {
+            let paid_amount =
+                self.take_from_reserve(farm_id, &reward.token_identifier, &reward.amount);
+            if paid_amount < reward.amount {
+                self.defer_rewards(
+                    farm_id,
+                    recipient,
+                    &reward.token_identifier,
+                    &(&reward.amount - &paid_amount),
+                );
+            }
+
+            let net_amount =
+                self.charge_reward_fee(farm_id, recipient, &reward.token_identifier, &paid_amount);
+            if net_amount > 0 {
+                self.event_rewards_claimed(
+                    farm_id,
+                    caller,
+                    recipient,
+                    &reward.token_identifier,
+                    &net_amount,
+                );
+            }
+
+            paid_rewards.push(EgldOrEsdtTokenPayment::new(
+                reward.token_identifier.clone(),
+                0,
+                net_amount,
+            ));
+        }
+        token_merged_data.rewards = paid_rewards;
+
+        self.burn_share_tokens(farm_id, &token_merged_data.token_burns);
+        self.boosted_share_supply(farm_id)
+            .update(|supply| *supply -= &token_merged_data.boosted_supply);
 
-        self.burn_share_tokens(&token_merged_data.token_burns);
+        token_merged_data
+    }
 
-        self.send().direct(
+    /// Takes up to `amount` out of the reserve and returns the amount taken.
+    fn take_from_reserve(
+        &self,
+        farm_id: FarmId,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> BigUint {
+        let reserve = self.rewards_reserve(farm_id, reward_token).get();
+        let taken = if reserve < *amount {
+            reserve.clone()
+        } else {
+            amount.clone()
+        };
+        if taken == 0 {
+            return taken;
+        }
+
+        self.rewards_reserve(farm_id, reward_token)
+            .set(reserve - &taken);
+        self.settle_accrued_rewards(farm_id, reward_token, &taken);
+
+        taken
+    }
+
+    fn defer_rewards(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+        reward_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        let mut unpaid_rewards = self.unpaid_rewards(farm_id, address);
+        let unpaid_amount = unpaid_rewards.get(reward_token).unwrap_or_default() + amount;
+        unpaid_rewards.insert(reward_token.clone(), unpaid_amount);
+
+        self.event_rewards_deferred(farm_id, address, reward_token, amount);
+    }
+
+    fn send_rewards(&self, to: &ManagedAddress, rewards: &ManagedVec<EgldOrEsdtTokenPayment>) {
+        let mut payments = ManagedVec::<Self::Api, EgldOrEsdtTokenPayment>::new();
+        for reward in rewards.iter() {
+            if reward.amount > 0 {
+                payments.push(reward.clone());
+            }
+        }
+
+        if !payments.is_empty() {
+            self.tx().to(to).payment(&payments).transfer();
+        }
+    }
+
+    /// Moves the rewards paid in the staked token into the position, net of the reward fee but
+    /// free of the stake fee; any other reward token is sent as usual.
+    fn compound_rewards(
+        &self,
+        farm_id: FarmId,
+        caller: &ManagedAddress,
+        current_timestamp_ms: u64,
+        transfers: &ManagedVec<EsdtTokenPayment>,
+    ) {
+        let staked_token = self.staked_token(farm_id).get();
+        require!(
+            self.reward_tokens(farm_id).contains(&staked_token),
+            ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN
+        );
+
+        let mut token_merged_data = self.collect_rewards(
+            farm_id,
             caller,
-            &self.reward_token().get(),
-            0,
-            &token_merged_data.reward_amount,
+            caller,
+            current_timestamp_ms,
+            transfers,
+            true,
         );
 
-        self.event_rewards_claimed(
-            &self.blockchain().get_caller(),
-            &token_merged_data.reward_amount,
+        let staked_reward_index = token_merged_data
+            .rewards
+            .iter()
+            .position(|reward| reward.token_identifier == staked_token)
+            .unwrap_or_default();
+        let mut staked_reward = token_merged_data.rewards.get(staked_reward_index).clone();
+        let compounded_amount = core::mem::replace(&mut staked_reward.amount, BigUint::zero());
+        let _ = token_merged_data
+            .rewards
+            .set(staked_reward_index, staked_reward);
+        self.send_rewards(caller, &token_merged_data.rewards);
+
+        self.increase_staked_supply(farm_id, caller, &compounded_amount);
+
+        let new_supply = &token_merged_data.token_supply + &compounded_amount;
+        self.reissue_share_token(
+            farm_id,
+            caller,
+            token_merged_data.kept_nonce,
+            &new_supply,
+            token_merged_data.lock_until_ts_ms,
+            token_merged_data.multiplier_bps,
+            current_timestamp_ms,
         );
 
-        token_merged_data
+        self.event_compounded(farm_id, caller, &compounded_amount, &new_supply);
     }
 
     fn get_share_token_attributes(
         &self,
+        farm_id: FarmId,
         address: &ManagedAddress,
         nonce: u64,
-    ) -> ShareTokenAttributes {
-        ShareTokenAttributes {
-            update_ts_ms: self
-                .blockchain()
-                .get_esdt_token_data(address, &self.share_token().get_token_id(), nonce)
-                .attributes
-                .parse_as_u64()
-                .unwrap_or(0),
+    ) -> ShareTokenAttributes<Self::Api> {
+        self.blockchain()
+            .get_esdt_token_data(address, &self.share_token(farm_id).get_token_id(), nonce)
+            .decode_attributes()
+    }
+
+    fn mint_share_token(
+        &self,
+        farm_id: FarmId,
+        to: &ManagedAddress,
+        amount: &BigUint,
+        lock_until_ts_ms: u64,
+        multiplier_bps: u64,
+        update_ts_ms: u64,
+    ) {
+        self.boosted_share_supply(farm_id)
+            .update(|supply| *supply += amount * multiplier_bps / MAX_PERCENTAGE);
+
+        let payment = self.share_token(farm_id).nft_create_and_send(
+            to,
+            amount.clone(),
+            &self.attributes_to_buffer(farm_id, update_ts_ms, lock_until_ts_ms, multiplier_bps),
+        );
+        self.share_token_nonce_supply(farm_id, payment.token_nonce)
+            .set(amount);
+        self.record_share_token_boost(
+            farm_id,
+            payment.token_nonce,
+            lock_until_ts_ms,
+            multiplier_bps,
+        );
+    }
+
+    /// Updates the attributes and quantity of `kept_nonce` in place and sends it back, so the position
+    /// keeps its nonce. Falls back to minting a new share token when no nonce could be kept.
+    #[allow(clippy::too_many_arguments)]
+    fn reissue_share_token(
+        &self,
+        farm_id: FarmId,
+        to: &ManagedAddress,
+        kept_nonce: u64,
+        amount: &BigUint,
+        lock_until_ts_ms: u64,
+        multiplier_bps: u64,
+        update_ts_ms: u64,
+    ) {
+        if kept_nonce == 0 {
+            if *amount > 0 {
+                self.mint_share_token(
+                    farm_id,
+                    to,
+                    amount,
+                    lock_until_ts_ms,
+                    multiplier_bps,
+                    update_ts_ms,
+                );
+            }
+            return;
+        }
+
+        let share_token = self.share_token(farm_id);
+        let kept_supply = self.share_token_nonce_supply(farm_id, kept_nonce).get();
+        if *amount > kept_supply {
+            share_token.nft_add_quantity(kept_nonce, amount - &kept_supply);
+        } else if *amount < kept_supply {
+            share_token.nft_burn(kept_nonce, &(&kept_supply - amount));
+        }
+
+        if *amount == 0 {
+            self.share_token_nonce_supply(farm_id, kept_nonce).clear();
+            self.record_share_token_boost(farm_id, kept_nonce, 0, MAX_PERCENTAGE);
+            return;
+        }
+
+        self.share_token_nonce_supply(farm_id, kept_nonce)
+            .set(amount);
+        self.record_share_token_boost(farm_id, kept_nonce, lock_until_ts_ms, multiplier_bps);
+        self.boosted_share_supply(farm_id)
+            .update(|supply| *supply += amount * multiplier_bps / MAX_PERCENTAGE);
+
+        share_token.nft_update_attributes(
+            kept_nonce,
+            &self.attributes_to_buffer(farm_id, update_ts_ms, lock_until_ts_ms, multiplier_bps),
+        );
+        self.tx()
+            .to(to)
+            .single_esdt(&share_token.get_token_id(), kept_nonce, amount)
+            .transfer();
+    }
+
+    /// Tracks the lock of a boosted `Pool` nonce until `settleExpiredBoost` takes the boost off the
+    /// boosted share supply; minting or updating the nonce resets any earlier settlement.
+    fn record_share_token_boost(
+        &self,
+        farm_id: FarmId,
+        nonce: u64,
+        lock_until_ts_ms: u64,
+        multiplier_bps: u64,
+    ) {
+        self.expired_boost_acc_reward_per_share(farm_id, nonce)
+            .clear();
+
+        let share_token_boost = self.share_token_boost(farm_id, nonce);
+        if multiplier_bps > MAX_PERCENTAGE
+            && self.emission_mode(farm_id).get() == EmissionMode::Pool
+        {
+            share_token_boost.set(ShareTokenBoost {
+                lock_until_ts_ms,
+                multiplier_bps,
+            });
+        } else {
+            share_token_boost.clear();
         }
     }
 
-    fn attributes_to_buffer(&self, update_ts_ms: u64) -> ManagedBuffer {
-        let share_token_attributes = ShareTokenAttributes { update_ts_ms };
+    /// Attributes are shared by every holder of a nonce, so only a nonce sent in full can be updated in place.
+    fn is_whole_share_token_nonce(&self, farm_id: FarmId, nonce: u64, amount: &BigUint) -> bool {
+        let supply = self.share_token_nonce_supply(farm_id, nonce).get();
+
+        supply > 0 && supply == *amount
+    }
+
+    fn attributes_to_buffer(
+        &self,
+        farm_id: FarmId,
+        update_ts_ms: u64,
+        lock_until_ts_ms: u64,
+        multiplier_bps: u64,
+    ) -> ManagedBuffer {
+        let mut acc_reward_per_share = ManagedVec::new();
+        for reward_token in self.reward_tokens(farm_id).iter() {
+            acc_reward_per_share.push(self.get_acc_reward_per_share(
+                farm_id,
+                &reward_token,
+                update_ts_ms,
+            ));
+        }
+
+        let share_token_attributes = ShareTokenAttributes::<Self::Api> {
+            update_ts_ms,
+            acc_reward_per_share,
+            lock_until_ts_ms,
+            multiplier_bps,
+        };
 
         let mut attributes = ManagedBuffer::new();
         let _ = share_token_attributes.top_encode(&mut attributes);
@@ -113,83 +594,356 @@ pub trait RewardsModule:
 
     fn prepare_rewards_and_burns(
         &self,
+        farm_id: FarmId,
         share_token_transfers: &ManagedVec<EsdtTokenPayment>,
         timestamp_ms: u64,
     ) -> ShareTokenMergedDataWithBurns<Self::Api> {
-        let mut total_rewards_amount: BigUint = BigUint::zero();
+        let mut total_rewards = self.empty_rewards(farm_id);
         let mut total_token_supply: BigUint = BigUint::zero();
+        let mut total_boosted_supply: BigUint = BigUint::zero();
+        let mut lock_until_ts_ms = 0;
+        let mut multiplier_bps = MAX_PERCENTAGE;
         let mut share_tokens: ManagedVec<ShareToken<Self::Api>> = ManagedVec::new();
+        let mut kept_nonce = 0;
 
-        let share_token_id = self.share_token().get_token_id();
+        let share_token_id = self.share_token(farm_id).get_token_id();
         for share_token_transfer in share_token_transfers.iter() {
             let (token, nonce, amount) = share_token_transfer.clone().into_tuple();
 
             require!(token == share_token_id, ERROR_INVALID_SHARE_TOKEN);
 
             let share_token_merged_data = self.calculate_reward(
+                farm_id,
                 &self.blockchain().get_sc_address(),
                 nonce,
                 &amount,
                 timestamp_ms,
             );
 
-            total_rewards_amount += share_token_merged_data.reward_amount;
+            self.add_rewards(&mut total_rewards, &share_token_merged_data.rewards);
+            self.settle_expired_boost(farm_id, nonce, &amount, timestamp_ms);
+            multiplier_bps = self.merge_multiplier_bps(
+                &total_token_supply,
+                multiplier_bps,
+                &share_token_merged_data.token_supply,
+                share_token_merged_data.multiplier_bps,
+            );
+            lock_until_ts_ms =
+                core::cmp::max(lock_until_ts_ms, share_token_merged_data.lock_until_ts_ms);
             total_token_supply += share_token_merged_data.token_supply;
-            share_tokens.push(ShareToken { nonce, amount });
+            total_boosted_supply += share_token_merged_data.boosted_supply;
+            if kept_nonce == 0 && self.is_whole_share_token_nonce(farm_id, nonce, &amount) {
+                kept_nonce = nonce;
+            } else {
+                share_tokens.push(ShareToken { nonce, amount });
+            }
         }
 
         ShareTokenMergedDataWithBurns {
             update_ts_ms: timestamp_ms,
             token_supply: total_token_supply,
-            reward_amount: total_rewards_amount,
+            boosted_supply: total_boosted_supply,
+            lock_until_ts_ms,
+            multiplier_bps,
+            rewards: total_rewards,
             token_burns: share_tokens,
+            kept_nonce,
         }
     }
 
     fn calculate_reward(
         &self,
+        farm_id: FarmId,
         address: &ManagedAddress,
         token_nonce: u64,
         amount: &BigUint,
         timestamp_ms: u64,
     ) -> ShareTokenMergedData<Self::Api> {
-        let share_token_attributes = self.get_share_token_attributes(address, token_nonce);
-
-        let rewards_per_sec = self.reward_per_sec().get();
-        let rewards_per_share = self.reward_per_share().get();
+        let share_token_attributes = self.get_share_token_attributes(farm_id, address, token_nonce);
+        let emission_mode = self.emission_mode(farm_id).get();
+        let expired_boost_acc_reward_per_share =
+            self.expired_boost_acc_reward_per_share(farm_id, token_nonce);
+
+        let mut rewards = ManagedVec::new();
+        for (index, reward_token) in self.reward_tokens(farm_id).iter().enumerate() {
+            let acc_reward_per_share =
+                self.get_acc_reward_per_share(farm_id, &reward_token, timestamp_ms);
+            // reward tokens added after the share token was minted have no snapshot and start from zero
+            let paid_acc_reward_per_share = match emission_mode {
+                EmissionMode::FixedRate => self.get_acc_reward_per_share(
+                    farm_id,
+                    &reward_token,
+                    share_token_attributes.update_ts_ms,
+                ),
+                EmissionMode::Pool => share_token_attributes
+                    .acc_reward_per_share
+                    .try_get(index)
+                    .map(|paid| paid.clone())
+                    .unwrap_or_default(),
+            };
+
+            let reward_amount = match emission_mode {
+                // the boost only covers the accrual up to the end of the lock
+                EmissionMode::FixedRate => {
+                    let boost_end_ts_ms = share_token_attributes
+                        .lock_until_ts_ms
+                        .max(share_token_attributes.update_ts_ms)
+                        .min(timestamp_ms);
+                    let boost_end_acc_reward_per_share =
+                        self.get_acc_reward_per_share(farm_id, &reward_token, boost_end_ts_ms);
+
+                    self.accrued_reward(
+                        &paid_acc_reward_per_share,
+                        &boost_end_acc_reward_per_share,
+                        amount,
+                        share_token_attributes.multiplier_bps,
+                    ) + self.accrued_reward(
+                        &boost_end_acc_reward_per_share,
+                        &acc_reward_per_share,
+                        amount,
+                        MAX_PERCENTAGE,
+                    )
+                }
+                // the pool accumulator has no history, so the boost lasts until it is settled or
+                // the position is re-minted; reward tokens added after the settlement start unboosted
+                EmissionMode::Pool => {
+                    let boost_end_acc_reward_per_share =
+                        if expired_boost_acc_reward_per_share.is_empty() {
+                            acc_reward_per_share.clone()
+                        } else {
+                            expired_boost_acc_reward_per_share
+                                .get()
+                                .try_get(index)
+                                .map(|settled| settled.clone())
+                                .unwrap_or_default()
+                        };
+
+                    self.accrued_reward(
+                        &paid_acc_reward_per_share,
+                        &boost_end_acc_reward_per_share,
+                        amount,
+                        share_token_attributes.multiplier_bps,
+                    ) + self.accrued_reward(
+                        &boost_end_acc_reward_per_share.max(paid_acc_reward_per_share),
+                        &acc_reward_per_share,
+                        amount,
+                        MAX_PERCENTAGE,
+                    )
+                }
+            };
+
+            rewards.push(EgldOrEsdtTokenPayment::new(reward_token, 0, reward_amount));
+        }
 
-        let rewarded_seconds = if timestamp_ms > share_token_attributes.update_ts_ms {
-            (timestamp_ms - share_token_attributes.update_ts_ms) / 1000
+        // the boost is dropped once the position is re-minted after its lock ended
+        let multiplier_bps = if share_token_attributes.lock_until_ts_ms > timestamp_ms {
+            share_token_attributes.multiplier_bps
         } else {
-            0
+            MAX_PERCENTAGE
         };
 
-        let reward_amount = &rewards_per_sec * rewarded_seconds * amount / &rewards_per_share;
+        // a settled boost has already been taken off the boosted share supply
+        let boosted_multiplier_bps = if expired_boost_acc_reward_per_share.is_empty() {
+            share_token_attributes.multiplier_bps
+        } else {
+            MAX_PERCENTAGE
+        };
 
         ShareTokenMergedData {
             update_ts_ms: timestamp_ms,
             token_supply: amount.clone(),
-            reward_amount,
+            boosted_supply: amount * boosted_multiplier_bps / MAX_PERCENTAGE,
+            lock_until_ts_ms: share_token_attributes.lock_until_ts_ms,
+            multiplier_bps,
+            rewards,
+        }
+    }
+
+    /// The accrued rewards count a `FixedRate` position at its boosted supply until it is
+    /// re-minted; takes off what the boost would have earned past the end of the lock. `Pool`
+    /// boosts are settled by `settleExpiredBoost` instead.
+    fn settle_expired_boost(
+        &self,
+        farm_id: FarmId,
+        nonce: u64,
+        amount: &BigUint,
+        timestamp_ms: u64,
+    ) {
+        if self.emission_mode(farm_id).get() != EmissionMode::FixedRate {
+            return;
+        }
+
+        let share_token_attributes =
+            self.get_share_token_attributes(farm_id, &self.blockchain().get_sc_address(), nonce);
+        if share_token_attributes.multiplier_bps <= MAX_PERCENTAGE
+            || share_token_attributes.lock_until_ts_ms >= timestamp_ms
+        {
+            return;
+        }
+
+        let boost_end_ts_ms = core::cmp::max(
+            share_token_attributes.lock_until_ts_ms,
+            share_token_attributes.update_ts_ms,
+        );
+        for reward_token in self.reward_tokens(farm_id).iter() {
+            let expired_boost = self.accrued_reward(
+                &self.get_acc_reward_per_share(farm_id, &reward_token, boost_end_ts_ms),
+                &self.get_acc_reward_per_share(farm_id, &reward_token, timestamp_ms),
+                amount,
+                share_token_attributes.multiplier_bps - MAX_PERCENTAGE,
+            );
+            self.settle_accrued_rewards(farm_id, &reward_token, &expired_boost);
+        }
+    }
+
+    fn accrued_reward(
+        &self,
+        from_acc_reward_per_share: &BigUint,
+        to_acc_reward_per_share: &BigUint,
+        amount: &BigUint,
+        multiplier_bps: u64,
+    ) -> BigUint {
+        if to_acc_reward_per_share <= from_acc_reward_per_share {
+            return BigUint::zero();
+        }
+
+        (to_acc_reward_per_share - from_acc_reward_per_share) * amount * multiplier_bps
+            / (BigUint::from(WAD) * MAX_PERCENTAGE)
+    }
+
+    /// Averages the multipliers weighted by supply, so that merging positions keeps their boosted supply.
+    fn merge_multiplier_bps(
+        &self,
+        supply: &BigUint,
+        multiplier_bps: u64,
+        other_supply: &BigUint,
+        other_multiplier_bps: u64,
+    ) -> u64 {
+        let total_supply = supply + other_supply;
+        if total_supply == 0 {
+            return MAX_PERCENTAGE;
+        }
+
+        let weighted_supply = supply * multiplier_bps + other_supply * other_multiplier_bps;
+        (weighted_supply / total_supply)
+            .to_u64()
+            .unwrap_or(MAX_PERCENTAGE)
+    }
+
+    fn empty_rewards(&self, farm_id: FarmId) -> ManagedVec<EgldOrEsdtTokenPayment> {
+        let mut rewards = ManagedVec::new();
+        for reward_token in self.reward_tokens(farm_id).iter() {
+            rewards.push(EgldOrEsdtTokenPayment::new(
+                reward_token,
+                0,
+                BigUint::zero(),
+            ));
+        }
+
+        rewards
+    }
+
+    /// Both vectors hold one entry per reward token, in `reward_tokens` order.
+    fn add_rewards(
+        &self,
+        total: &mut ManagedVec<EgldOrEsdtTokenPayment>,
+        rewards: &ManagedVec<EgldOrEsdtTokenPayment>,
+    ) {
+        for (index, reward) in rewards.iter().enumerate() {
+            let mut merged = total.get(index).clone();
+            merged.amount += &reward.amount;
+            let _ = total.set(index, merged);
         }
     }
 
-    fn burn_share_tokens(&self, burns: &ManagedVec<ShareToken<Self::Api>>) {
+    fn burn_share_tokens(&self, farm_id: FarmId, burns: &ManagedVec<ShareToken<Self::Api>>) {
         for burn in burns.iter() {
-            self.burn_share_token(&burn);
+            self.burn_share_token(farm_id, &burn);
         }
     }
 
-    fn burn_share_token(&self, token_burn: &ShareToken<Self::Api>) {
-        self.share_token()
+    fn burn_share_token(&self, farm_id: FarmId, token_burn: &ShareToken<Self::Api>) {
+        self.share_token(farm_id)
             .nft_burn(token_burn.nonce, &token_burn.amount);
+
+        let nonce_supply = self.share_token_nonce_supply(farm_id, token_burn.nonce);
+        if !nonce_supply.is_empty() {
+            nonce_supply.update(|supply| *supply -= &token_burn.amount);
+        }
     }
 
+    // === Storage ===
+
+    /// Lock of each boosted `Pool` nonce whose boost has not been settled yet.
+    #[storage_mapper("share_token_boost")]
+    fn share_token_boost(&self, farm_id: FarmId, nonce: u64) -> SingleValueMapper<ShareTokenBoost>;
+
+    /// Reward accumulators, in `reward_tokens` order, at which the boost of a nonce was settled.
+    #[storage_mapper("expired_boost_acc_reward_per_share")]
+    fn expired_boost_acc_reward_per_share(
+        &self,
+        farm_id: FarmId,
+        nonce: u64,
+    ) -> SingleValueMapper<ManagedVec<BigUint>>;
+
+    /// Outstanding supply of each share token nonce. Nonces minted before it was tracked are never
+    /// updated in place and go through the burn and re-mint flow instead.
+    #[storage_mapper("share_token_nonce_supply")]
+    fn share_token_nonce_supply(&self, farm_id: FarmId, nonce: u64) -> SingleValueMapper<BigUint>;
+
+    /// Rewards owed to an address that the reserve could not pay when they were collected.
+    /// They stay counted in the farm's accrued rewards until paid.
+    #[storage_mapper("unpaid_rewards")]
+    fn unpaid_rewards(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+    ) -> MapMapper<EgldOrEsdtTokenIdentifier, BigUint>;
+
     // === Events ===
 
     #[event("rewardsClaimed")]
     fn event_rewards_claimed(
         &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] beneficiary: &ManagedAddress,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("rewardsDeferred")]
+    fn event_rewards_deferred(
+        &self,
+        #[indexed] farm_id: FarmId,
         #[indexed] address: &ManagedAddress,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
         #[indexed] amount: &BigUint,
     );
+
+    #[event("unpaidRewardsClaimed")]
+    fn event_unpaid_rewards_claimed(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] reward_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("compounded")]
+    fn event_compounded(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] compounded_amount: &BigUint,
+        #[indexed] share_token_supply: &BigUint,
+    );
+
+    #[event("boostSettled")]
+    fn event_boost_settled(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] nonce: u64,
+        #[indexed] expired_boost: &BigUint,
+    );
 }
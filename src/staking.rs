@@ -1,69 +1,249 @@
 use errors::{
-    ERROR_INVALID_SHARE_TOKEN, ERROR_ONLY_ONE_STAKING_TRANSFER_ALLOWED, ERROR_STAKED_TOKEN_MISSING,
-    ERROR_UNSTAKE_AMOUNT_EXCEEDS, ERROR_ZERO_AMOUNT,
+    ERROR_INVALID_SHARE_TOKEN, ERROR_ONLY_ONE_STAKING_TRANSFER_ALLOWED, ERROR_POSITION_LOCKED,
+    ERROR_STAKED_TOKEN_MISSING, ERROR_UNSTAKE_AMOUNT_EXCEEDS, ERROR_ZERO_AMOUNT,
 };
 use multiversx_sc::imports::*;
+use structs::{FarmId, PenaltySource, ShareToken};
 
 #[multiversx_sc::module]
 pub trait StakingModule:
     crate::admins::AdminsModule
     + crate::pause::PauseModule
+    + crate::timelock::TimelockModule
     + crate::farm::FarmModule
+    + crate::fees::FeesModule
+    + crate::locking::LockingModule
     + crate::rewards::RewardsModule
+    + crate::penalty::PenaltyModule
+    + crate::unbonding::UnbondingModule
 {
     // === Endpoints ===
 
     #[payable]
     #[endpoint(stake)]
-    fn stake(&self) {
+    fn stake(&self, farm_id: FarmId, opt_lock_period_ms: OptionalValue<u64>) {
+        let caller = self.blockchain().get_caller();
+        self.process_stake(farm_id, &caller, opt_lock_period_ms);
+    }
+
+    /// Stakes the caller's tokens into a position owned by `recipient`.
+    #[payable]
+    #[endpoint(stakeFor)]
+    fn stake_for(
+        &self,
+        farm_id: FarmId,
+        recipient: ManagedAddress,
+        opt_lock_period_ms: OptionalValue<u64>,
+    ) {
+        self.process_stake(farm_id, &recipient, opt_lock_period_ms);
+    }
+
+    #[payable]
+    #[endpoint(unstake)]
+    fn unstake(&self, opt_unstake_amount: OptionalValue<BigUint>) {
+        let caller = self.blockchain().get_caller();
+        self.process_unstake(&caller, opt_unstake_amount);
+    }
+
+    /// Unstakes the caller's position, delivering the principal and rewards to `recipient`.
+    #[payable]
+    #[endpoint(unstakeTo)]
+    fn unstake_to(&self, recipient: ManagedAddress, opt_unstake_amount: OptionalValue<BigUint>) {
+        self.process_unstake(&recipient, opt_unstake_amount);
+    }
+
+    /// Burns the share tokens and returns their principal, forfeiting their rewards to the
+    /// reserve. The principal goes through the farm's unbonding period unless emergency mode is
+    /// on, which also lets locked positions exit and keeps the endpoint available while paused.
+    #[payable]
+    #[endpoint(emergencyUnstake)]
+    fn emergency_unstake(&self) {
+        self.require_not_paused_or_emergency();
+
+        let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
+        let caller = self.blockchain().get_caller();
+        let transfers = self.call_value().all_esdt_transfers();
+        let farm_id = self.get_farm_id_by_share_tokens(&transfers);
+
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
+
+        let token_merged_data =
+            self.prepare_rewards_and_burns(farm_id, &transfers, current_timestamp_ms);
+        require!(
+            token_merged_data.lock_until_ts_ms <= current_timestamp_ms
+                || self.emergency_mode().get(),
+            ERROR_POSITION_LOCKED
+        );
+
+        for transfer in transfers.iter() {
+            self.burn_share_token(
+                farm_id,
+                &ShareToken {
+                    nonce: transfer.token_nonce,
+                    amount: transfer.amount.clone(),
+                },
+            );
+        }
+        for reward in token_merged_data.rewards.iter() {
+            self.settle_accrued_rewards(farm_id, &reward.token_identifier, &reward.amount);
+        }
+
+        self.boosted_share_supply(farm_id)
+            .update(|supply| *supply -= &token_merged_data.boosted_supply);
+        self.decrease_staked_supply(farm_id, &caller, &token_merged_data.token_supply);
+
+        if self.unbonding_period_ms(farm_id).get() > 0 && !self.emergency_mode().get() {
+            self.start_unbonding(
+                farm_id,
+                &caller,
+                &token_merged_data.token_supply,
+                current_timestamp_ms,
+            );
+        } else {
+            self.send().direct(
+                &caller,
+                &self.staked_token(farm_id).get(),
+                0,
+                &token_merged_data.token_supply,
+            );
+        }
+
+        self.event_emergency_unstaked(
+            farm_id,
+            &caller,
+            &token_merged_data.token_supply,
+            &token_merged_data.rewards,
+        );
+    }
+
+    /// Stakes back the tokens of a pending unbonding into a new share token, free of the stake
+    /// fee they already paid.
+    #[endpoint(cancelUnbonding)]
+    fn cancel_unbonding(
+        &self,
+        farm_id: FarmId,
+        unbonding_id: u64,
+        opt_lock_period_ms: OptionalValue<u64>,
+    ) {
         self.require_not_paused();
+        self.require_farm_exists(farm_id);
+
+        let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
+        let caller = self.blockchain().get_caller();
+
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
+
+        let unbonding = self.take_unbonding(farm_id, &caller, unbonding_id);
+        let (lock_until_ts_ms, multiplier_bps) =
+            self.get_lock_terms(farm_id, opt_lock_period_ms, current_timestamp_ms);
+
+        self.increase_staked_supply(farm_id, &caller, &unbonding.amount);
+
+        self.mint_share_token(
+            farm_id,
+            &caller,
+            &unbonding.amount,
+            lock_until_ts_ms,
+            multiplier_bps,
+            current_timestamp_ms,
+        );
+
+        self.event_unbonding_cancelled(farm_id, &caller, &unbonding);
+    }
+
+    // === Private ===
+
+    fn process_stake(
+        &self,
+        farm_id: FarmId,
+        recipient: &ManagedAddress,
+        opt_lock_period_ms: OptionalValue<u64>,
+    ) {
+        self.require_not_paused();
+        self.require_farm_exists(farm_id);
 
         let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
         let caller = self.blockchain().get_caller();
         let transfers = self.call_value().all_transfers();
 
-        let staked_token = self.staked_token().get();
-        let share_token = self.share_token().get_token_id();
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
+
+        let staked_token = self.staked_token(farm_id).get();
+        let share_token = self.share_token(farm_id).get_token_id();
         let (staking_transfer, share_transfers) =
             self.split_transfers(&transfers, &staked_token, &share_token);
 
-        let mut new_staked_amount = staking_transfer.amount.clone();
-        require!(new_staked_amount > BigUint::zero(), ERROR_ZERO_AMOUNT);
+        require!(staking_transfer.amount > BigUint::zero(), ERROR_ZERO_AMOUNT);
+        let staked_amount =
+            self.charge_stake_fee(farm_id, recipient, &staked_token, &staking_transfer.amount);
+        let mut new_staked_amount = staked_amount.clone();
+
+        let (mut lock_until_ts_ms, mut multiplier_bps) =
+            self.get_lock_terms(farm_id, opt_lock_period_ms, current_timestamp_ms);
 
-        let mut rewards_claimed = BigUint::zero();
+        let mut rewards_claimed = ManagedVec::new();
+        let mut kept_nonce = 0;
 
         if !share_transfers.is_empty() {
-            let token_merged_data =
-                self.claim_rewards(&caller, current_timestamp_ms, &share_transfers);
+            let token_merged_data = self.claim_rewards(
+                farm_id,
+                &caller,
+                recipient,
+                current_timestamp_ms,
+                &share_transfers,
+                false,
+            );
 
+            multiplier_bps = self.merge_multiplier_bps(
+                &new_staked_amount,
+                multiplier_bps,
+                &token_merged_data.token_supply,
+                token_merged_data.multiplier_bps,
+            );
+            lock_until_ts_ms = core::cmp::max(lock_until_ts_ms, token_merged_data.lock_until_ts_ms);
             new_staked_amount += token_merged_data.token_supply;
-            rewards_claimed = token_merged_data.reward_amount;
+            rewards_claimed = token_merged_data.rewards;
+            kept_nonce = token_merged_data.kept_nonce;
         }
 
-        self.share_token().nft_create_and_send(
-            &caller,
-            new_staked_amount.clone(),
-            &self.attributes_to_buffer(current_timestamp_ms),
+        self.increase_staked_supply(farm_id, recipient, &staked_amount);
+
+        self.reissue_share_token(
+            farm_id,
+            recipient,
+            kept_nonce,
+            &new_staked_amount,
+            lock_until_ts_ms,
+            multiplier_bps,
+            current_timestamp_ms,
         );
 
         self.event_staked(
+            farm_id,
             &caller,
-            &staking_transfer.amount,
+            recipient,
+            &staked_amount,
             &new_staked_amount,
             &rewards_claimed,
         );
     }
 
-    #[payable]
-    #[endpoint(unstake)]
-    fn unstake(&self, opt_unstake_amount: OptionalValue<BigUint>) {
+    fn process_unstake(
+        &self,
+        recipient: &ManagedAddress,
+        opt_unstake_amount: OptionalValue<BigUint>,
+    ) {
         self.require_not_paused();
 
         let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
         let caller = self.blockchain().get_caller();
         let transfers = self.call_value().all_esdt_transfers();
+        let farm_id = self.get_farm_id_by_share_tokens(&transfers);
+
+        self.update_acc_reward_per_share(farm_id, current_timestamp_ms);
 
-        let token_merged_data = self.claim_rewards(&caller, current_timestamp_ms, &transfers);
+        let mut token_merged_data =
+            self.prepare_rewards_and_burns(farm_id, &transfers, current_timestamp_ms);
 
         let unstake_amount = match opt_unstake_amount {
             OptionalValue::Some(amount) => {
@@ -76,27 +256,82 @@ pub trait StakingModule:
             OptionalValue::None => token_merged_data.token_supply.clone(),
         };
 
-        if token_merged_data.token_supply > unstake_amount {
-            self.share_token().nft_create_and_send(
-                &caller,
-                &token_merged_data.token_supply - &unstake_amount,
-                &self.attributes_to_buffer(current_timestamp_ms),
+        // a rewards penalty applies to the whole reward, before any shortfall is deferred
+        let mut unstaked_payout = unstake_amount.clone();
+        let mut opt_penalty_charge = None;
+        if token_merged_data.lock_until_ts_ms > current_timestamp_ms {
+            require!(
+                !self.early_unstake_penalty(farm_id).is_empty(),
+                ERROR_POSITION_LOCKED
             );
+
+            let penalty = self.early_unstake_penalty(farm_id).get();
+            let penalties = self.compute_early_unstake_penalty(
+                farm_id,
+                &penalty,
+                &unstake_amount,
+                &token_merged_data.rewards,
+            );
+            match penalty.source {
+                PenaltySource::Principal => unstaked_payout -= &penalties.get(0).amount,
+                PenaltySource::Rewards => {
+                    for (index, penalty_payment) in penalties.iter().enumerate() {
+                        let mut reward = token_merged_data.rewards.get(index).clone();
+                        reward.amount -= &penalty_payment.amount;
+                        let _ = token_merged_data.rewards.set(index, reward);
+                    }
+                }
+            }
+            opt_penalty_charge = Some((penalty, penalties));
+        }
+
+        let token_merged_data =
+            self.pay_collected_rewards(farm_id, &caller, recipient, token_merged_data);
+
+        if let Some((penalty, mut penalties)) = opt_penalty_charge {
+            if penalty.source == PenaltySource::Rewards {
+                penalties = self.take_reward_penalties(farm_id, &penalties);
+            }
+
+            self.route_early_unstake_penalty(farm_id, &penalty.destination, &penalties);
+            self.event_early_unstake_penalty_charged(farm_id, &caller, &penalties);
         }
 
-        self.send()
-            .direct(&caller, &self.staked_token().get(), 0, &unstake_amount);
+        self.send_rewards(recipient, &token_merged_data.rewards);
+
+        self.decrease_staked_supply(farm_id, &caller, &unstake_amount);
+
+        self.reissue_share_token(
+            farm_id,
+            &caller,
+            token_merged_data.kept_nonce,
+            &(&token_merged_data.token_supply - &unstake_amount),
+            token_merged_data.lock_until_ts_ms,
+            token_merged_data.multiplier_bps,
+            current_timestamp_ms,
+        );
+
+        if self.unbonding_period_ms(farm_id).get() > 0 {
+            self.start_unbonding(farm_id, recipient, &unstaked_payout, current_timestamp_ms);
+        } else {
+            self.send().direct(
+                recipient,
+                &self.staked_token(farm_id).get(),
+                0,
+                &unstaked_payout,
+            );
+        }
 
         self.event_unstaked(
+            farm_id,
             &caller,
+            recipient,
             &unstake_amount,
             &token_merged_data.token_supply,
-            &token_merged_data.reward_amount,
+            &token_merged_data.rewards,
         );
     }
 
-    // === Private ===
-
     fn split_transfers(
         &self,
         transfers: &ManagedVec<EgldOrEsdtTokenPayment>,
@@ -121,7 +356,7 @@ pub trait StakingModule:
         }
 
         (
-            staking_transfer.expect(ERROR_STAKED_TOKEN_MISSING),
+            staking_transfer.unwrap_or_else(|| sc_panic!(ERROR_STAKED_TOKEN_MISSING)),
             share_transfers,
         )
     }
@@ -131,18 +366,31 @@ pub trait StakingModule:
     #[event("staked")]
     fn event_staked(
         &self,
-        #[indexed] address: &ManagedAddress,
+        #[indexed] farm_id: FarmId,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] beneficiary: &ManagedAddress,
         #[indexed] staked_amount: &BigUint,
         #[indexed] share_token_supply: &BigUint,
-        #[indexed] reward_amount: &BigUint,
+        #[indexed] rewards: &ManagedVec<EgldOrEsdtTokenPayment>,
+    );
+
+    #[event("emergencyUnstaked")]
+    fn event_emergency_unstaked(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] unstaked_amount: &BigUint,
+        #[indexed] forfeited_rewards: &ManagedVec<EgldOrEsdtTokenPayment>,
     );
 
     #[event("unstaked")]
     fn event_unstaked(
         &self,
-        #[indexed] address: &ManagedAddress,
+        #[indexed] farm_id: FarmId,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] beneficiary: &ManagedAddress,
         #[indexed] unstaked_amount: &BigUint,
         #[indexed] share_token_supply: &BigUint,
-        #[indexed] reward_amount: &BigUint,
+        #[indexed] rewards: &ManagedVec<EgldOrEsdtTokenPayment>,
     );
 }
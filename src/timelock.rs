@@ -0,0 +1,160 @@
+use errors::{ERROR_ACTION_NOT_FOUND, ERROR_ACTION_NOT_READY, ERROR_APPROVAL_QUORUM_NOT_REACHED};
+use multiversx_sc::imports::*;
+use structs::{PendingAction, TimelockAction};
+
+/// Smart Contract module that holds sensitive changes for a delay before they take effect.
+///
+/// It provides:
+/// * an endpoint where the owner can set the timelock delay, decreases going through the
+///   timelock at the current delay
+/// * an endpoint where the owner can cancel a pending action
+/// * views to read the delay and list the pending actions
+/// * methods to queue an action and take it once its delay has elapsed
+///
+/// When an approval threshold is set, actions also wait for enough admins to approve them.
+/// Executing the actions is left to the modules that queue them.
+#[multiversx_sc::module]
+pub trait TimelockModule: crate::admins::AdminsModule {
+    // === Endpoints ===
+
+    /// Delay applied to the actions queued from now on; `0` applies them right away. A shorter
+    /// delay is itself queued behind the current one.
+    #[endpoint(setTimelockDelay)]
+    fn set_timelock_delay(&self, delay_ms: u64) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        if delay_ms < self.timelock_delay_ms().get() {
+            self.queue_action(TimelockAction::SetTimelockDelay { delay_ms });
+        } else {
+            self.apply_set_timelock_delay(delay_ms);
+        }
+    }
+
+    #[endpoint(cancelAction)]
+    fn cancel_action(&self, action_id: u64) {
+        self.require_is_owner(&self.blockchain().get_caller());
+
+        require!(
+            self.pending_actions().remove(&action_id).is_some(),
+            ERROR_ACTION_NOT_FOUND
+        );
+        self.action_approvers(action_id).clear();
+
+        self.event_action_cancelled(action_id);
+    }
+
+    // === Views ===
+
+    #[view(getPendingActions)]
+    fn get_pending_actions(&self) -> MultiValueEncoded<PendingAction<Self::Api>> {
+        let mut pending_actions = MultiValueEncoded::new();
+        for pending_action in self.pending_actions().values() {
+            pending_actions.push(pending_action);
+        }
+
+        pending_actions
+    }
+
+    // === Private ===
+
+    fn is_timelocked(&self) -> bool {
+        self.timelock_delay_ms().get() > 0
+    }
+
+    fn apply_set_timelock_delay(&self, delay_ms: u64) {
+        self.timelock_delay_ms().set(delay_ms);
+
+        self.event_timelock_delay_set(delay_ms);
+    }
+
+    fn require_action_pending(&self, action_id: u64) {
+        require!(
+            self.pending_actions().contains_key(&action_id),
+            ERROR_ACTION_NOT_FOUND
+        );
+    }
+
+    fn queue_action(&self, action: TimelockAction<Self::Api>) {
+        let id = self.last_action_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        let proposer = self.blockchain().get_caller();
+        if self.requires_approvals() && self.is_admin(&proposer) {
+            self.record_approval(id, &proposer);
+        }
+
+        let pending_action = PendingAction {
+            id,
+            proposer,
+            eta_ms: self.blockchain().get_block_timestamp_ms() + self.timelock_delay_ms().get(),
+            action,
+        };
+
+        self.event_action_queued(
+            id,
+            &pending_action.proposer,
+            pending_action.eta_ms,
+            &pending_action.action,
+        );
+        self.pending_actions().insert(id, pending_action);
+    }
+
+    /// Removes a pending action whose delay has elapsed and whose approvals reached the
+    /// threshold, and returns it for execution.
+    fn take_ready_action(&self, action_id: u64) -> TimelockAction<Self::Api> {
+        let pending_action = self
+            .pending_actions()
+            .remove(&action_id)
+            .unwrap_or_else(|| sc_panic!(ERROR_ACTION_NOT_FOUND));
+        require!(
+            pending_action.eta_ms <= self.blockchain().get_block_timestamp_ms(),
+            ERROR_ACTION_NOT_READY
+        );
+        require!(
+            self.has_approval_quorum(action_id),
+            ERROR_APPROVAL_QUORUM_NOT_REACHED
+        );
+        self.action_approvers(action_id).clear();
+
+        self.event_action_executed(action_id, &self.blockchain().get_caller());
+
+        pending_action.action
+    }
+
+    // === Storage ===
+
+    #[view(getTimelockDelay)]
+    #[storage_mapper("timelock_delay_ms")]
+    fn timelock_delay_ms(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("last_action_id")]
+    fn last_action_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("pending_actions")]
+    fn pending_actions(&self) -> MapMapper<u64, PendingAction<Self::Api>>;
+
+    // === Events ===
+
+    #[event("timelockDelaySet")]
+    fn event_timelock_delay_set(&self, #[indexed] delay_ms: u64);
+
+    #[event("actionQueued")]
+    fn event_action_queued(
+        &self,
+        #[indexed] action_id: u64,
+        #[indexed] proposer: &ManagedAddress,
+        #[indexed] eta_ms: u64,
+        action: &TimelockAction<Self::Api>,
+    );
+
+    #[event("actionCancelled")]
+    fn event_action_cancelled(&self, #[indexed] action_id: u64);
+
+    #[event("actionExecuted")]
+    fn event_action_executed(
+        &self,
+        #[indexed] action_id: u64,
+        #[indexed] executor: &ManagedAddress,
+    );
+}
@@ -0,0 +1,181 @@
+use errors::{ERROR_NOTHING_TO_WITHDRAW, ERROR_UNBONDING_NOT_FOUND};
+use multiversx_sc::imports::*;
+use structs::{FarmId, Role, Unbonding};
+
+/// Smart Contract module that offers an unbonding period for unstaked tokens.
+///
+/// It provides:
+/// * an endpoint where a schedule manager can set the unbonding period of a farm
+/// * an endpoint to withdraw the unbondings whose period has passed
+/// * views to list the pending unbondings of an address
+/// * methods to start and take out unbondings
+///
+/// With no unbonding period set, `unstake` sends the staked tokens right away.
+#[multiversx_sc::module]
+pub trait UnbondingModule:
+    crate::admins::AdminsModule
+    + crate::pause::PauseModule
+    + crate::timelock::TimelockModule
+    + crate::farm::FarmModule
+{
+    // === Endpoints ===
+
+    #[endpoint(setUnbondingPeriod)]
+    fn set_unbonding_period(&self, farm_id: FarmId, unbonding_period_ms: u64) {
+        self.require_has_role(Role::ScheduleManager, &self.blockchain().get_caller());
+        self.require_farm_exists(farm_id);
+
+        self.unbonding_period_ms(farm_id).set(unbonding_period_ms);
+
+        self.event_unbonding_period_set(farm_id, unbonding_period_ms);
+    }
+
+    #[endpoint(withdraw)]
+    fn withdraw(&self, farm_id: FarmId) {
+        self.require_not_paused_or_emergency();
+        self.require_farm_exists(farm_id);
+
+        let current_timestamp_ms = self.blockchain().get_block_timestamp_ms();
+        let caller = self.blockchain().get_caller();
+
+        let mut unlocked_ids = ManagedVec::<Self::Api, u64>::new();
+        let mut amount = BigUint::zero();
+        for (id, unbonding) in self.unbondings(farm_id, &caller).iter() {
+            if unbonding.unlock_ts_ms <= current_timestamp_ms {
+                unlocked_ids.push(id);
+                amount += unbonding.amount;
+            }
+        }
+        require!(!unlocked_ids.is_empty(), ERROR_NOTHING_TO_WITHDRAW);
+
+        for id in unlocked_ids.iter() {
+            self.unbondings(farm_id, &caller).remove(&id);
+        }
+        self.total_unbonding(farm_id)
+            .update(|total| *total -= &amount);
+
+        self.send()
+            .direct(&caller, &self.staked_token(farm_id).get(), 0, &amount);
+
+        self.event_withdrawn(farm_id, &caller, &amount);
+    }
+
+    // === Views ===
+
+    #[view(getUnbondingPeriod)]
+    fn get_unbonding_period(&self, farm_id: FarmId) -> u64 {
+        self.unbonding_period_ms(farm_id).get()
+    }
+
+    #[view(getUnbondings)]
+    fn get_unbondings(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+    ) -> MultiValueEncoded<Unbonding<Self::Api>> {
+        self.unbondings(farm_id, address).values().collect()
+    }
+
+    #[view(getTotalUnbonding)]
+    fn get_total_unbonding(&self, farm_id: FarmId) -> BigUint {
+        self.total_unbonding(farm_id).get()
+    }
+
+    // === Private ===
+
+    fn start_unbonding(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+        amount: &BigUint,
+        current_timestamp_ms: u64,
+    ) -> Unbonding<Self::Api> {
+        let id = self.last_unbonding_id(farm_id).update(|id| {
+            *id += 1;
+            *id
+        });
+        let unbonding = Unbonding {
+            id,
+            amount: amount.clone(),
+            unlock_ts_ms: current_timestamp_ms + self.unbonding_period_ms(farm_id).get(),
+        };
+
+        self.unbondings(farm_id, address)
+            .insert(id, unbonding.clone());
+        self.total_unbonding(farm_id)
+            .update(|total| *total += amount);
+
+        self.event_unbonding_started(farm_id, address, &unbonding);
+
+        unbonding
+    }
+
+    fn take_unbonding(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+        id: u64,
+    ) -> Unbonding<Self::Api> {
+        let unbonding = self
+            .unbondings(farm_id, address)
+            .remove(&id)
+            .unwrap_or_else(|| sc_panic!(ERROR_UNBONDING_NOT_FOUND));
+
+        self.total_unbonding(farm_id)
+            .update(|total| *total -= &unbonding.amount);
+
+        unbonding
+    }
+
+    // === Storage ===
+
+    #[storage_mapper("unbonding_period_ms")]
+    fn unbonding_period_ms(&self, farm_id: FarmId) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("last_unbonding_id")]
+    fn last_unbonding_id(&self, farm_id: FarmId) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("unbondings")]
+    fn unbondings(
+        &self,
+        farm_id: FarmId,
+        address: &ManagedAddress,
+    ) -> MapMapper<u64, Unbonding<Self::Api>>;
+
+    /// Staked tokens held for pending unbondings, no longer counted in `total_staked`.
+    #[storage_mapper("total_unbonding")]
+    fn total_unbonding(&self, farm_id: FarmId) -> SingleValueMapper<BigUint>;
+
+    // === Events ===
+
+    #[event("unbondingPeriodSet")]
+    fn event_unbonding_period_set(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] unbonding_period_ms: u64,
+    );
+
+    #[event("unbondingStarted")]
+    fn event_unbonding_started(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        unbonding: &Unbonding<Self::Api>,
+    );
+
+    #[event("unbondingCancelled")]
+    fn event_unbonding_cancelled(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        unbonding: &Unbonding<Self::Api>,
+    );
+
+    #[event("withdrawn")]
+    fn event_withdrawn(
+        &self,
+        #[indexed] farm_id: FarmId,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+    );
+}
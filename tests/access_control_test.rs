@@ -0,0 +1,143 @@
+mod setup;
+
+use errors::{ERROR_MISSING_ROLE, ERROR_NOT_PAUSED, ERROR_PAUSED};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+#[test]
+fn pause_blocks_user_endpoints() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.pause(OWNER);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.stake_in_farm_expect_error(USER, FARM_ID, STAKED_TOKEN, STAKE_AMOUNT, ERROR_PAUSED);
+    state.claim_rewards_expect_error(USER, 1, STAKE_AMOUNT, ERROR_PAUSED);
+    state.unstake_expect_error(USER, 1, STAKE_AMOUNT, ERROR_PAUSED);
+    state.withdraw_expect_error(USER, ERROR_PAUSED);
+
+    state.unpause(OWNER);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 10));
+}
+
+#[test]
+fn pause_and_unpause_require_matching_state() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .unpause()
+        .with_result(ExpectError(4, as_str(ERROR_NOT_PAUSED)))
+        .run();
+
+    state.pause(OWNER);
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .pause()
+        .with_result(ExpectError(4, as_str(ERROR_PAUSED)))
+        .run();
+}
+
+#[test]
+fn admin_endpoints_reject_non_admins() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .pause()
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .modify_rewards(
+            FARM_ID,
+            BigUint::from(REWARD_PER_SEC * 2),
+            BigUint::from(REWARD_PER_SHARE),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_rewards(
+            FARM_ID,
+            BigUint::from(1u64),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_lock_tier(FARM_ID, 10_000u64, 12_000u64)
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+}
+
+#[test]
+fn only_owner_manages_admins() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .add_admin(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    state.add_admin(ADMIN);
+    assert!(is_admin(&mut state, ADMIN));
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .remove_admin(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .run();
+    assert!(!is_admin(&mut state, ADMIN));
+}
+
+fn is_admin(state: &mut TokenStakingTestState, address: TestAddress) -> bool {
+    state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .is_admin(address.to_address())
+        .returns(ReturnsResult)
+        .run()
+}
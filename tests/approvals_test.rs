@@ -0,0 +1,178 @@
+mod setup;
+
+use errors::{
+    ERROR_ACTION_ALREADY_APPROVED, ERROR_ACTION_NOT_FOUND, ERROR_ALREADY_ENDED,
+    ERROR_APPROVAL_QUORUM_NOT_REACHED, ERROR_INVALID_APPROVAL_THRESHOLD, ERROR_NOT_ADMIN,
+};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const DELAY_MS: u64 = 20_000;
+
+fn new_state_with_two_of_two() -> TokenStakingTestState {
+    let mut state = TokenStakingTestState::new();
+    state.add_admin(ADMIN);
+    state.set_approval_threshold(2);
+
+    state
+}
+
+#[test]
+fn action_executes_once_threshold_is_reached() {
+    let mut state = new_state_with_two_of_two();
+
+    state.modify_rewards(REWARD_PER_SEC * 2, REWARD_PER_SHARE);
+    assert_eq!(state.get_pending_actions().len(), 1);
+    assert_eq!(state.get_action_approvers(1), vec![OWNER.to_address()]);
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).reward_per_sec,
+        REWARD_PER_SEC
+    );
+
+    state.execute_action_expect_error(USER, 1, ERROR_APPROVAL_QUORUM_NOT_REACHED);
+
+    state.approve_action(ADMIN, 1);
+    assert_eq!(state.get_pending_actions().len(), 1);
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).reward_per_sec,
+        REWARD_PER_SEC
+    );
+
+    state.execute_action(USER, 1);
+    assert!(state.get_pending_actions().is_empty());
+    assert!(state.get_action_approvers(1).is_empty());
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).reward_per_sec,
+        REWARD_PER_SEC * 2
+    );
+
+    state.approve_action_expect_error(ADMIN, 1, ERROR_ACTION_NOT_FOUND);
+}
+
+#[test]
+fn approvals_are_checked() {
+    let mut state = new_state_with_two_of_two();
+
+    state.withdraw_reward_token(REWARD_TOKEN, 1_000);
+
+    state.approve_action_expect_error(OWNER, 1, ERROR_ACTION_ALREADY_APPROVED);
+    state.approve_action_expect_error(USER, 1, ERROR_NOT_ADMIN);
+    state.approve_action_expect_error(ADMIN, 2, ERROR_ACTION_NOT_FOUND);
+
+    state.add_admin(OTHER_USER);
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .remove_admin(MultiValueVec::from(vec![OWNER.to_address()]))
+        .run();
+    state.approve_action(ADMIN, 1);
+    state.execute_action_expect_error(USER, 1, ERROR_APPROVAL_QUORUM_NOT_REACHED);
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).rewards_reserve,
+        INITIAL_REWARD_RESERVE
+    );
+}
+
+#[test]
+fn approval_is_kept_when_the_action_fails() {
+    let mut state = new_state_with_two_of_two();
+
+    state.set_block_timestamp_ms(END_TS_MS - 10_000);
+    state.modify_end_ts(END_TS_MS + 10_000);
+    state.set_block_timestamp_ms(END_TS_MS + 10_000);
+
+    state.approve_action(ADMIN, 1);
+    assert_eq!(
+        state.get_action_approvers(1),
+        vec![OWNER.to_address(), ADMIN.to_address()]
+    );
+    state.execute_action_expect_error(USER, 1, ERROR_ALREADY_ENDED);
+    assert_eq!(state.get_pending_actions().len(), 1);
+}
+
+#[test]
+fn admins_cannot_drop_below_threshold() {
+    let mut state = new_state_with_two_of_two();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .remove_admin(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .with_result(ExpectError(4, as_str(ERROR_INVALID_APPROVAL_THRESHOLD)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .renounce_admin()
+        .with_result(ExpectError(4, as_str(ERROR_INVALID_APPROVAL_THRESHOLD)))
+        .run();
+
+    state.set_approval_threshold(1);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .renounce_admin()
+        .run();
+}
+
+#[test]
+fn approved_action_still_waits_for_timelock_delay() {
+    let mut state = new_state_with_two_of_two();
+    state.set_timelock_delay(DELAY_MS);
+
+    state.modify_end_ts(END_TS_MS + 10_000);
+    state.approve_action(ADMIN, 1);
+    assert_eq!(state.get_pending_actions().len(), 1);
+    assert_eq!(state.get_farm_info().rewards.get(0).end_ts_ms, END_TS_MS);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + DELAY_MS);
+    state.execute_action(USER, 1);
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).end_ts_ms,
+        END_TS_MS + 10_000
+    );
+}
+
+#[test]
+fn threshold_is_managed_by_owner() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_approval_threshold(1usize)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_approval_threshold(2usize)
+        .with_result(ExpectError(4, as_str(ERROR_INVALID_APPROVAL_THRESHOLD)))
+        .run();
+
+    state.set_approval_threshold(1);
+    state.modify_rewards(REWARD_PER_SEC * 2, REWARD_PER_SHARE);
+    assert!(state.get_pending_actions().is_empty());
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).reward_per_sec,
+        REWARD_PER_SEC * 2
+    );
+}
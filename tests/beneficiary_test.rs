@@ -0,0 +1,57 @@
+mod setup;
+
+use setup::*;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+#[test]
+fn stake_for_delivers_position_to_recipient() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake_for(USER, OTHER_USER, STAKE_AMOUNT);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT);
+    state.check_share_token(OTHER_USER, 1, STAKE_AMOUNT, DEPLOY_TS_MS);
+    assert_eq!(state.get_user_staked(USER), 0u64.into());
+    assert_eq!(state.get_user_staked(OTHER_USER), STAKE_AMOUNT.into());
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.claim_rewards(OTHER_USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(OTHER_USER, expected_rewards(STAKE_AMOUNT, 10));
+}
+
+#[test]
+fn unstake_to_sends_principal_and_rewards_to_recipient() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake_to(USER, ADMIN, 1, STAKE_AMOUNT, 400);
+
+    state
+        .world
+        .check_account(ADMIN)
+        .esdt_balance(STAKED_TOKEN, 400)
+        .esdt_balance(REWARD_TOKEN, expected_rewards(STAKE_AMOUNT, 20));
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT);
+    state.check_share_token(USER, 1, STAKE_AMOUNT - 400, START_TS_MS + 20_000);
+    assert_eq!(state.get_user_staked(USER), (STAKE_AMOUNT - 400).into());
+}
+
+#[test]
+fn claim_rewards_to_keeps_position_with_caller() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.claim_rewards_to(USER, ADMIN, 1, STAKE_AMOUNT);
+
+    state
+        .world
+        .check_account(ADMIN)
+        .esdt_balance(REWARD_TOKEN, expected_rewards(STAKE_AMOUNT, 20));
+    state.check_reward_balance(USER, 0);
+    state.check_share_token(USER, 1, STAKE_AMOUNT, START_TS_MS + 20_000);
+}
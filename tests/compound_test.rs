@@ -0,0 +1,95 @@
+mod setup;
+
+use errors::ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN;
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+fn new_state_with_staked_token_rewards() -> TokenStakingTestState {
+    let mut state = TokenStakingTestState::new();
+
+    state.add_reward_token(
+        STAKED_TOKEN,
+        START_TS_MS,
+        END_TS_MS,
+        REWARD_PER_SEC,
+        REWARD_PER_SHARE,
+    );
+    state.deposit_reward_token(OWNER, STAKED_TOKEN, INITIAL_REWARD_RESERVE);
+    state.stake(USER, STAKE_AMOUNT);
+
+    state
+}
+
+#[test]
+fn compound_moves_staked_token_rewards_into_position() {
+    let mut state = new_state_with_staked_token_rewards();
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.compound(USER, 1, STAKE_AMOUNT);
+
+    let compounded = expected_rewards(STAKE_AMOUNT, 20);
+    state.check_share_token(USER, 1, STAKE_AMOUNT + compounded, START_TS_MS + 20_000);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 20));
+    assert_eq!(state.get_total_staked(), (STAKE_AMOUNT + compounded).into());
+    assert_eq!(
+        state.get_user_staked(USER),
+        (STAKE_AMOUNT + compounded).into()
+    );
+
+    let reserve = state
+        .get_farm_info()
+        .rewards
+        .iter()
+        .find(|reward| reward.reward_token == STAKED_TOKEN.to_token_identifier())
+        .map(|reward| reward.rewards_reserve.clone())
+        .unwrap();
+    assert_eq!(reserve, INITIAL_REWARD_RESERVE - compounded);
+}
+
+#[test]
+fn compounded_position_earns_on_increased_amount() {
+    let mut state = new_state_with_staked_token_rewards();
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.claim_and_compound(USER, 1, STAKE_AMOUNT);
+
+    let new_amount = STAKE_AMOUNT + expected_rewards(STAKE_AMOUNT, 20);
+    state.set_block_timestamp_ms(START_TS_MS + 30_000);
+    state.unstake(USER, 1, new_amount);
+
+    state.check_staked_balance(
+        USER,
+        INITIAL_STAKED_BALANCE
+            + expected_rewards(STAKE_AMOUNT, 20)
+            + expected_rewards(new_amount, 10),
+    );
+    state.check_reward_balance(
+        USER,
+        expected_rewards(STAKE_AMOUNT, 20) + expected_rewards(new_amount, 10),
+    );
+}
+
+#[test]
+fn compound_requires_staked_token_to_be_rewarded() {
+    let mut state = TokenStakingTestState::new();
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .compound()
+        .single_esdt(
+            &SHARE_TOKEN.to_token_identifier(),
+            1,
+            &BigUint::from(STAKE_AMOUNT),
+        )
+        .with_result(ExpectError(4, as_str(ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN)))
+        .run();
+}
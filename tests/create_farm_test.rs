@@ -0,0 +1,189 @@
+mod setup;
+
+use errors::{
+    ERROR_FARM_NOT_FOUND, ERROR_WRONG_ISSUANCE_AMOUNT, ERROR_WRONG_REWARD_VALUES,
+    ERROR_WRONG_START_TS, ERROR_WRONG_TIMEFRAME,
+};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::EmissionMode;
+use tokenstaking::farm::FarmModule;
+
+fn create_farm_expect_error(
+    state: &mut TokenStakingTestState,
+    from: TestAddress,
+    start_ts_ms: u64,
+    end_ts_ms: u64,
+    reward_per_share: u64,
+    issuance_cost: u64,
+    error: &str,
+) {
+    state
+        .world
+        .tx()
+        .from(from)
+        .to(SC)
+        .typed(TemplateProxy)
+        .create_farm(
+            STAKED_TOKEN.to_token_identifier(),
+            REWARD_TOKEN.to_token_identifier(),
+            start_ts_ms,
+            end_ts_ms,
+            BigUint::from(REWARD_PER_SEC),
+            BigUint::from(reward_per_share),
+            ManagedBuffer::from("StakingShare"),
+            ManagedBuffer::from("SHARE"),
+            OptionalValue::<EmissionMode>::None,
+        )
+        .egld(issuance_cost)
+        .with_result(ExpectError(4, error))
+        .run();
+}
+
+#[test]
+fn create_farm_registers_farm_and_share_token() {
+    let mut state = TokenStakingTestState::new();
+
+    let farm = state.get_farm_info();
+    assert_eq!(farm.farm_id, FARM_ID);
+    assert_eq!(
+        farm.staked_token,
+        EgldOrEsdtTokenIdentifier::esdt(STAKED_TOKEN.to_token_identifier())
+    );
+    assert_eq!(farm.share_token, SHARE_TOKEN.to_token_identifier());
+    assert_eq!(farm.emission_mode, EmissionMode::FixedRate);
+    assert_eq!(farm.total_staked, 0u64);
+
+    let reward = farm.rewards.get(0).clone();
+    assert_eq!(
+        reward.reward_token,
+        EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN.to_token_identifier())
+    );
+    assert_eq!(reward.rewards_reserve, INITIAL_REWARD_RESERVE);
+    assert_eq!(
+        (reward.start_ts_ms, reward.end_ts_ms),
+        (START_TS_MS, END_TS_MS)
+    );
+
+    state
+        .world
+        .check_account(OWNER)
+        .balance(TOKEN_ISSUANCE_COST * 9);
+}
+
+#[test]
+fn create_farm_validates_arguments() {
+    let mut state = TokenStakingTestState::deploy();
+
+    create_farm_expect_error(
+        &mut state,
+        OWNER,
+        START_TS_MS,
+        END_TS_MS,
+        REWARD_PER_SHARE,
+        TOKEN_ISSUANCE_COST - 1,
+        as_str(ERROR_WRONG_ISSUANCE_AMOUNT),
+    );
+    create_farm_expect_error(
+        &mut state,
+        OWNER,
+        END_TS_MS,
+        START_TS_MS,
+        REWARD_PER_SHARE,
+        TOKEN_ISSUANCE_COST,
+        as_str(ERROR_WRONG_TIMEFRAME),
+    );
+    create_farm_expect_error(
+        &mut state,
+        OWNER,
+        DEPLOY_TS_MS - 1,
+        END_TS_MS,
+        REWARD_PER_SHARE,
+        TOKEN_ISSUANCE_COST,
+        as_str(ERROR_WRONG_START_TS),
+    );
+    create_farm_expect_error(
+        &mut state,
+        OWNER,
+        START_TS_MS,
+        END_TS_MS,
+        0,
+        TOKEN_ISSUANCE_COST,
+        as_str(ERROR_WRONG_REWARD_VALUES),
+    );
+
+    state
+        .world
+        .check_account(OWNER)
+        .balance(TOKEN_ISSUANCE_COST * 10);
+}
+
+#[test]
+fn only_owner_can_create_farm() {
+    let mut state = TokenStakingTestState::deploy();
+    state.add_admin(ADMIN);
+    state.world.set_egld_balance(ADMIN, TOKEN_ISSUANCE_COST);
+
+    create_farm_expect_error(
+        &mut state,
+        ADMIN,
+        START_TS_MS,
+        END_TS_MS,
+        REWARD_PER_SHARE,
+        TOKEN_ISSUANCE_COST,
+        "Endpoint can only be called by owner",
+    );
+}
+
+#[test]
+fn failed_issuance_refunds_owner() {
+    let mut state = TokenStakingTestState::deploy();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .egld(TOKEN_ISSUANCE_COST)
+        .whitebox(tokenstaking::contract_obj, |sc| {
+            sc.last_farm_id().set(FARM_ID);
+            sc.share_token_issuance_callback(
+                FARM_ID,
+                &OWNER.to_managed_address(),
+                &EgldOrEsdtTokenIdentifier::esdt(STAKED_TOKEN.to_token_identifier()),
+                &EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN.to_token_identifier()),
+                START_TS_MS,
+                END_TS_MS,
+                &BigUint::from(REWARD_PER_SEC),
+                &BigUint::from(REWARD_PER_SHARE),
+                EmissionMode::FixedRate,
+                ManagedAsyncCallResult::Err(ManagedAsyncCallError {
+                    err_code: 1,
+                    err_msg: ManagedBuffer::from("issue failed"),
+                }),
+            );
+        });
+
+    state
+        .world
+        .check_account(OWNER)
+        .balance(TOKEN_ISSUANCE_COST * 10);
+    state.world.check_account(SC).balance(0);
+
+    state
+        .world
+        .query()
+        .to(SC)
+        .whitebox(tokenstaking::contract_obj, |sc| {
+            assert!(sc.share_token(FARM_ID).is_empty());
+            assert!(!sc.farm_ids().contains(&FARM_ID));
+        });
+    state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .get_farm_info(FARM_ID)
+        .with_result(ExpectError(4, as_str(ERROR_FARM_NOT_FOUND)))
+        .run();
+}
@@ -0,0 +1,286 @@
+mod setup;
+
+use constants::{MAX_EARLY_UNSTAKE_PENALTY_BPS, MAX_PERCENTAGE};
+use errors::{
+    ERROR_FARM_NOT_FOUND, ERROR_MISSING_BURN_ROLE, ERROR_POSITION_LOCKED,
+    ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN, ERROR_WRONG_PENALTY,
+};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::{PenaltyDestination, PenaltySource};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const LOCK_PERIOD_MS: u64 = 150_000;
+const PENALTY_BPS: u64 = 1_000;
+
+fn new_state_with_locked_stake() -> TokenStakingTestState {
+    let mut state = TokenStakingTestState::new();
+    state.set_lock_tier(LOCK_PERIOD_MS, MAX_PERCENTAGE);
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS);
+
+    state
+}
+
+#[test]
+fn principal_penalty_is_sent_to_configured_address() {
+    let mut state = new_state_with_locked_stake();
+    state.set_early_unstake_penalty(
+        PENALTY_BPS,
+        PenaltySource::Principal,
+        PenaltyDestination::Address(ADMIN.to_managed_address()),
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    let penalty = STAKE_AMOUNT * PENALTY_BPS / MAX_PERCENTAGE;
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - penalty);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 20));
+    state
+        .world
+        .check_account(ADMIN)
+        .esdt_balance(STAKED_TOKEN, penalty);
+    assert_eq!(state.get_total_staked(), 0u64.into());
+}
+
+#[test]
+fn principal_penalty_can_be_burned() {
+    let mut state = TokenStakingTestState::new();
+    state
+        .world
+        .set_esdt_local_roles(SC, STAKED_TOKEN.as_bytes(), &[EsdtLocalRole::Burn]);
+    state.set_lock_tier(LOCK_PERIOD_MS, MAX_PERCENTAGE);
+    state.set_early_unstake_penalty(
+        PENALTY_BPS,
+        PenaltySource::Principal,
+        PenaltyDestination::Burn,
+    );
+
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_staked_balance(
+        USER,
+        INITIAL_STAKED_BALANCE - STAKE_AMOUNT * PENALTY_BPS / MAX_PERCENTAGE,
+    );
+    state
+        .world
+        .check_account(SC)
+        .esdt_balance(STAKED_TOKEN, STAKE_AMOUNT);
+}
+
+#[test]
+fn rewards_penalty_is_redistributed_into_reserve() {
+    let mut state = new_state_with_locked_stake();
+    state.set_early_unstake_penalty(
+        PENALTY_BPS,
+        PenaltySource::Rewards,
+        PenaltyDestination::Redistribute,
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    let rewards = expected_rewards(STAKE_AMOUNT, 20);
+    let penalty = rewards * PENALTY_BPS / MAX_PERCENTAGE;
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_reward_balance(USER, rewards - penalty);
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).rewards_reserve,
+        INITIAL_REWARD_RESERVE - rewards + penalty
+    );
+}
+
+#[test]
+fn penalty_is_only_charged_before_lock_ends() {
+    let mut state = new_state_with_locked_stake();
+    state.set_early_unstake_penalty(
+        PENALTY_BPS,
+        PenaltySource::Principal,
+        PenaltyDestination::Address(ADMIN.to_managed_address()),
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    let penalty = state.get_early_unstake_penalty(USER, 1, STAKE_AMOUNT);
+    assert_eq!(
+        penalty,
+        vec![(
+            EgldOrEsdtTokenIdentifier::esdt(STAKED_TOKEN.to_token_identifier()),
+            (STAKE_AMOUNT * PENALTY_BPS / MAX_PERCENTAGE).into()
+        )]
+    );
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + LOCK_PERIOD_MS);
+    assert!(state
+        .get_early_unstake_penalty(USER, 1, STAKE_AMOUNT)
+        .is_empty());
+
+    state.unstake(USER, 1, STAKE_AMOUNT);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+}
+
+#[test]
+fn min_stake_duration_locks_new_stakes() {
+    let mut state = TokenStakingTestState::new();
+    state.set_min_stake_duration(LOCK_PERIOD_MS);
+
+    state.stake(USER, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_share_token_lock(USER, 1),
+        (DEPLOY_TS_MS + LOCK_PERIOD_MS, MAX_PERCENTAGE)
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake_expect_error(USER, 1, STAKE_AMOUNT, ERROR_POSITION_LOCKED);
+
+    state.set_early_unstake_penalty(
+        PENALTY_BPS,
+        PenaltySource::Principal,
+        PenaltyDestination::Address(ADMIN.to_managed_address()),
+    );
+    state.unstake_partial(USER, 1, STAKE_AMOUNT, STAKE_AMOUNT / 2);
+    state.check_staked_balance(
+        USER,
+        INITIAL_STAKED_BALANCE - STAKE_AMOUNT / 2 - STAKE_AMOUNT / 2 * PENALTY_BPS / MAX_PERCENTAGE,
+    );
+}
+
+#[test]
+fn invalid_penalties_are_rejected() {
+    let mut state = TokenStakingTestState::new();
+
+    for penalty_bps in [0, MAX_EARLY_UNSTAKE_PENALTY_BPS + 1] {
+        state
+            .world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_early_unstake_penalty(
+                FARM_ID,
+                penalty_bps,
+                PenaltySource::Rewards,
+                PenaltyDestination::<StaticApi>::Redistribute,
+            )
+            .with_result(ExpectError(4, as_str(ERROR_WRONG_PENALTY)))
+            .run();
+    }
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_early_unstake_penalty(
+            FARM_ID,
+            PENALTY_BPS,
+            PenaltySource::Principal,
+            PenaltyDestination::<StaticApi>::Redistribute,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_STAKED_TOKEN_NOT_REWARD_TOKEN)))
+        .run();
+
+    // burning the principal needs the local burn role on the staked token
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_early_unstake_penalty(
+            FARM_ID,
+            PENALTY_BPS,
+            PenaltySource::Principal,
+            PenaltyDestination::<StaticApi>::Burn,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_BURN_ROLE)))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .remove_early_unstake_penalty(FARM_ID + 1)
+        .with_result(ExpectError(4, as_str(ERROR_FARM_NOT_FOUND)))
+        .run();
+}
+
+#[test]
+fn rewards_penalty_applies_to_deferred_rewards() {
+    let mut state = new_state_with_locked_stake();
+    state.set_early_unstake_penalty(
+        PENALTY_BPS,
+        PenaltySource::Rewards,
+        PenaltyDestination::Address(ADMIN.to_managed_address()),
+    );
+
+    let rewards = expected_rewards(STAKE_AMOUNT, 20);
+    let penalty = rewards * PENALTY_BPS / MAX_PERCENTAGE;
+    let reserve = (rewards - penalty) / 2;
+    state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE - reserve);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    assert_eq!(
+        state.get_early_unstake_penalty(USER, 1, STAKE_AMOUNT),
+        vec![(
+            EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN.to_token_identifier()),
+            0u64.into()
+        )]
+    );
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, reserve);
+    assert_eq!(
+        state.get_unpaid_rewards(USER),
+        (rewards - penalty - reserve).into()
+    );
+    state
+        .world
+        .check_account(ADMIN)
+        .esdt_balance(REWARD_TOKEN, 0u64);
+
+    state.deposit_rewards(OWNER, INITIAL_REWARD_RESERVE);
+    state.claim_unpaid_rewards(USER);
+    state.check_reward_balance(USER, rewards - penalty);
+    assert_eq!(state.get_accrued_rewards(), 0u64.into());
+}
+
+#[test]
+fn rewards_penalty_preview_is_capped_by_reserve() {
+    let mut state = new_state_with_locked_stake();
+    state.set_early_unstake_penalty(
+        PENALTY_BPS,
+        PenaltySource::Rewards,
+        PenaltyDestination::Address(ADMIN.to_managed_address()),
+    );
+
+    let rewards = expected_rewards(STAKE_AMOUNT, 20);
+    let penalty = rewards * PENALTY_BPS / MAX_PERCENTAGE;
+    state.withdraw_reward_token(
+        REWARD_TOKEN,
+        INITIAL_REWARD_RESERVE - (rewards - penalty / 2),
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    assert_eq!(
+        state.get_early_unstake_penalty(USER, 1, STAKE_AMOUNT),
+        vec![(
+            EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN.to_token_identifier()),
+            (penalty / 2).into()
+        )]
+    );
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, rewards - penalty);
+    state
+        .world
+        .check_account(ADMIN)
+        .esdt_balance(REWARD_TOKEN, penalty / 2);
+}
@@ -0,0 +1,145 @@
+mod setup;
+
+use constants::MAX_PERCENTAGE;
+use errors::{
+    ERROR_EMERGENCY_MODE, ERROR_MISSING_ROLE, ERROR_NOT_EMERGENCY_MODE, ERROR_PAUSED,
+    ERROR_POSITION_LOCKED,
+};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const LOCK_PERIOD_MS: u64 = 150_000;
+
+const UNBONDING_PERIOD_MS: u64 = 50_000;
+
+#[test]
+fn emergency_unstake_returns_principal_and_forfeits_rewards() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.emergency_unstake(USER, 1, STAKE_AMOUNT / 2);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT / 2);
+    state.check_reward_balance(USER, 0);
+    assert_eq!(state.get_share_token_balance(USER, 1), STAKE_AMOUNT / 2);
+    assert_eq!(state.get_user_staked(USER), (STAKE_AMOUNT / 2).into());
+    assert_eq!(state.get_total_staked(), (STAKE_AMOUNT * 3 / 2).into());
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).rewards_reserve,
+        INITIAL_REWARD_RESERVE
+    );
+    assert_eq!(
+        state.get_accrued_rewards(),
+        expected_rewards(STAKE_AMOUNT * 3 / 2, 10).into()
+    );
+
+    state.claim_rewards(OTHER_USER, 2, STAKE_AMOUNT);
+    state.check_reward_balance(OTHER_USER, expected_rewards(STAKE_AMOUNT, 10));
+}
+
+#[test]
+fn emergency_unstake_requires_emergency_mode_while_paused() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.pause(OWNER);
+
+    state.emergency_unstake_expect_error(USER, 1, STAKE_AMOUNT, ERROR_PAUSED);
+
+    state.set_emergency_mode(OWNER, true);
+    assert!(state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .emergency_mode()
+        .returns(ReturnsResult)
+        .run());
+
+    state.unstake_expect_error(USER, 1, STAKE_AMOUNT, ERROR_PAUSED);
+    state.emergency_unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    assert_eq!(state.get_total_staked(), 0u64.into());
+}
+
+#[test]
+fn locked_positions_need_emergency_mode() {
+    let mut state = TokenStakingTestState::new();
+    state.set_lock_tier(LOCK_PERIOD_MS, MAX_PERCENTAGE);
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.emergency_unstake_expect_error(USER, 1, STAKE_AMOUNT, ERROR_POSITION_LOCKED);
+
+    state.set_emergency_mode(OWNER, true);
+    state.emergency_unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_reward_balance(USER, 0);
+    assert_eq!(state.get_total_staked(), 0u64.into());
+}
+
+#[test]
+fn emergency_mode_is_admin_only() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .enable_emergency_mode()
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .disable_emergency_mode()
+        .with_result(ExpectError(4, as_str(ERROR_NOT_EMERGENCY_MODE)))
+        .run();
+
+    state.set_emergency_mode(OWNER, true);
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .enable_emergency_mode()
+        .with_result(ExpectError(4, as_str(ERROR_EMERGENCY_MODE)))
+        .run();
+
+    state.set_emergency_mode(OWNER, false);
+}
+
+#[test]
+fn emergency_unstake_unbonds_outside_emergency_mode() {
+    let mut state = TokenStakingTestState::new();
+    state.set_unbonding_period(UNBONDING_PERIOD_MS);
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.emergency_unstake(USER, 1, STAKE_AMOUNT);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT);
+    assert_eq!(state.get_unbondings(USER).len(), 1);
+
+    state.pause(OWNER);
+    state.set_emergency_mode(OWNER, true);
+    state.emergency_unstake(OTHER_USER, 2, STAKE_AMOUNT);
+    state.check_staked_balance(OTHER_USER, INITIAL_STAKED_BALANCE);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + UNBONDING_PERIOD_MS);
+    state.withdraw(USER);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+}
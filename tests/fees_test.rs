@@ -0,0 +1,208 @@
+mod setup;
+
+use constants::MAX_PROTOCOL_FEE_BPS;
+use errors::{ERROR_MISSING_ROLE, ERROR_NOTHING_TO_WITHDRAW, ERROR_NOT_OWNER, ERROR_WRONG_FEE};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const FEE_COLLECTOR: TestAddress = TestAddress::new("fee-collector");
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const REWARD_FEE_BPS: u64 = 1_000;
+const STAKE_FEE_BPS: u64 = 500;
+
+fn set_protocol_fees(state: &mut TokenStakingTestState, reward_fee_bps: u64, stake_fee_bps: u64) {
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_protocol_fees(reward_fee_bps, stake_fee_bps)
+        .run();
+}
+
+fn get_stake_fee(state: &mut TokenStakingTestState) -> u64 {
+    state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .stake_fee_bps()
+        .returns(ReturnsResult)
+        .run()
+}
+
+fn withdraw_fees(state: &mut TokenStakingTestState) {
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_fees(MultiValueVec::<EgldOrEsdtTokenIdentifier<StaticApi>>::new())
+        .run();
+}
+
+fn get_accrued_fees(state: &mut TokenStakingTestState, token: TestTokenIdentifier) -> RustBigUint {
+    let fees = state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .get_accrued_fees()
+        .returns(ReturnsResult)
+        .run()
+        .to_vec();
+
+    let token = EgldOrEsdtTokenIdentifier::esdt(token.to_token_identifier());
+    fees.iter()
+        .find(|fee| fee.token_identifier == token)
+        .map(|fee| RustBigUint::from_bytes_be(fee.amount.to_bytes_be().as_slice()))
+        .unwrap_or_default()
+}
+
+#[test]
+fn reward_fee_is_taken_from_claimed_rewards() {
+    let mut state = TokenStakingTestState::new();
+    set_protocol_fees(&mut state, REWARD_FEE_BPS, 0);
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+
+    let rewards = expected_rewards(STAKE_AMOUNT, 10);
+    let fee = rewards * REWARD_FEE_BPS / 10_000;
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, rewards - fee);
+    assert_eq!(get_accrued_fees(&mut state, REWARD_TOKEN), fee.into());
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).rewards_reserve,
+        INITIAL_REWARD_RESERVE - rewards
+    );
+    assert_eq!(state.get_accrued_rewards(), 0u64.into());
+}
+
+#[test]
+fn stake_fee_is_taken_from_principal() {
+    let mut state = TokenStakingTestState::new();
+    set_protocol_fees(&mut state, 0, STAKE_FEE_BPS);
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    let fee = STAKE_AMOUNT * STAKE_FEE_BPS / 10_000;
+    assert_eq!(state.get_share_token_balance(USER, 1), STAKE_AMOUNT - fee);
+    assert_eq!(state.get_user_staked(USER), (STAKE_AMOUNT - fee).into());
+    assert_eq!(state.get_total_staked(), (STAKE_AMOUNT - fee).into());
+    assert_eq!(get_accrued_fees(&mut state, STAKED_TOKEN), fee.into());
+
+    state.unstake(USER, 1, STAKE_AMOUNT - fee);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - fee);
+}
+
+#[test]
+fn stake_fee_is_not_charged_again_on_cancelled_unbonding() {
+    let mut state = TokenStakingTestState::new();
+    set_protocol_fees(&mut state, 0, STAKE_FEE_BPS);
+    state.set_unbonding_period(10_000);
+
+    let fee = STAKE_AMOUNT * STAKE_FEE_BPS / 10_000;
+    state.stake(USER, STAKE_AMOUNT);
+    state.unstake(USER, 1, STAKE_AMOUNT - fee);
+    state.cancel_unbonding(USER, 1);
+
+    assert_eq!(state.get_share_token_balance(USER, 2), STAKE_AMOUNT - fee);
+    assert_eq!(state.get_user_staked(USER), (STAKE_AMOUNT - fee).into());
+    assert_eq!(get_accrued_fees(&mut state, STAKED_TOKEN), fee.into());
+}
+
+#[test]
+fn fee_change_is_queued_behind_timelock() {
+    let mut state = TokenStakingTestState::new();
+    state.set_timelock_delay(20_000);
+
+    set_protocol_fees(&mut state, REWARD_FEE_BPS, STAKE_FEE_BPS);
+    assert_eq!(state.get_pending_actions().len(), 1);
+    assert_eq!(get_stake_fee(&mut state), 0);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + 20_000);
+    state.execute_action(USER, 1);
+    assert_eq!(get_stake_fee(&mut state), STAKE_FEE_BPS);
+}
+
+#[test]
+fn fees_are_withdrawn_to_fee_collector() {
+    let mut state = TokenStakingTestState::new();
+    state.world.account(FEE_COLLECTOR).nonce(1);
+    set_protocol_fees(&mut state, REWARD_FEE_BPS, STAKE_FEE_BPS);
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_fee_collector(FEE_COLLECTOR.to_address())
+        .run();
+
+    let stake_fee = STAKE_AMOUNT * STAKE_FEE_BPS / 10_000;
+    let reward_fee = expected_rewards(STAKE_AMOUNT - stake_fee, 10) * REWARD_FEE_BPS / 10_000;
+    state.stake(USER, STAKE_AMOUNT);
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT - stake_fee);
+
+    withdraw_fees(&mut state);
+
+    state
+        .world
+        .check_account(FEE_COLLECTOR)
+        .esdt_balance(REWARD_TOKEN, reward_fee)
+        .esdt_balance(STAKED_TOKEN, stake_fee);
+    assert_eq!(get_accrued_fees(&mut state, REWARD_TOKEN), 0u64.into());
+    assert_eq!(get_accrued_fees(&mut state, STAKED_TOKEN), 0u64.into());
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_fees(MultiValueVec::<EgldOrEsdtTokenIdentifier<StaticApi>>::new())
+        .with_result(ExpectError(4, as_str(ERROR_NOTHING_TO_WITHDRAW)))
+        .run();
+}
+
+#[test]
+fn fee_configuration_is_restricted() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_protocol_fees(REWARD_FEE_BPS, STAKE_FEE_BPS)
+        .with_result(ExpectError(4, as_str(ERROR_NOT_OWNER)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_protocol_fees(REWARD_FEE_BPS, MAX_PROTOCOL_FEE_BPS + 1)
+        .with_result(ExpectError(4, as_str(ERROR_WRONG_FEE)))
+        .run();
+    set_protocol_fees(&mut state, MAX_PROTOCOL_FEE_BPS, MAX_PROTOCOL_FEE_BPS);
+    assert_eq!(get_stake_fee(&mut state), MAX_PROTOCOL_FEE_BPS);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_fees(MultiValueVec::<EgldOrEsdtTokenIdentifier<StaticApi>>::new())
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+}
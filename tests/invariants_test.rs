@@ -0,0 +1,743 @@
+mod setup;
+
+use constants::{MAX_PERCENTAGE, WAD};
+use errors::{
+    ERROR_NOT_ENOUGH_REWARDS, ERROR_NO_EXPIRED_BOOST, ERROR_NO_REWARDS_APPLICABLE,
+    ERROR_NO_UNPAID_REWARDS, ERROR_POSITION_LOCKED,
+};
+use multiversx_sc_scenario::imports::*;
+use proptest::prelude::*;
+use setup::*;
+use structs::EmissionMode;
+use tokenstaking::{farm::FarmModule, rewards::RewardsModule};
+
+const USERS: [TestAddress; 4] = [
+    USER,
+    OTHER_USER,
+    TestAddress::new("third-user"),
+    TestAddress::new("fourth-user"),
+];
+
+const MAX_STAKE: u64 = 10_000;
+const MAX_REWARD_PER_SEC: u64 = 10;
+const MAX_INITIAL_RESERVE: u64 = 200_000;
+
+const SECOND_START_TS_MS: u64 = START_TS_MS + 50_000;
+const SECOND_END_TS_MS: u64 = END_TS_MS + 50_000;
+const SECOND_REWARD_PER_SEC: u64 = 5;
+const SECOND_REWARD_PER_SHARE: u64 = 100;
+
+const LOCK_PERIOD_MS: u64 = 10_000;
+const LOCK_MULTIPLIER_BPS: u64 = 15_000;
+
+#[derive(Debug, Clone)]
+enum Action {
+    Stake {
+        user: usize,
+        amount: u64,
+        merge: bool,
+        locked: bool,
+    },
+    Claim {
+        user: usize,
+        index: usize,
+    },
+    Unstake {
+        user: usize,
+        index: usize,
+        percent: u64,
+    },
+    SettleBoost {
+        index: usize,
+    },
+    ClaimUnpaid {
+        user: usize,
+    },
+    ModifyRewards {
+        token: usize,
+        reward_per_sec: u64,
+    },
+    Deposit {
+        token: usize,
+        amount: u64,
+    },
+    Withdraw {
+        token: usize,
+        percent: u64,
+    },
+    AdvanceTime {
+        seconds: u64,
+    },
+}
+
+fn action_strategy() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        3 => (0..USERS.len(), 1..=MAX_STAKE, any::<bool>(), any::<bool>()).prop_map(
+            |(user, amount, merge, locked)| Action::Stake { user, amount, merge, locked }
+        ),
+        2 => (0..USERS.len(), any::<usize>())
+            .prop_map(|(user, index)| Action::Claim { user, index }),
+        2 => (0..USERS.len(), any::<usize>(), 1..=100u64)
+            .prop_map(|(user, index, percent)| Action::Unstake { user, index, percent }),
+        2 => any::<usize>().prop_map(|index| Action::SettleBoost { index }),
+        1 => (0..USERS.len()).prop_map(|user| Action::ClaimUnpaid { user }),
+        1 => (any::<usize>(), 1..=MAX_REWARD_PER_SEC)
+            .prop_map(|(token, reward_per_sec)| Action::ModifyRewards { token, reward_per_sec }),
+        1 => (any::<usize>(), 1..=MAX_INITIAL_RESERVE)
+            .prop_map(|(token, amount)| Action::Deposit { token, amount }),
+        1 => (any::<usize>(), 1..=100u64)
+            .prop_map(|(token, percent)| Action::Withdraw { token, percent }),
+        3 => (0..=20u64).prop_map(|seconds| Action::AdvanceTime { seconds }),
+    ]
+}
+
+#[derive(Debug)]
+struct Position {
+    user: usize,
+    nonce: u64,
+    amount: u64,
+    update_ts_ms: u64,
+    lock_until_ts_ms: u64,
+    multiplier_bps: u64,
+    /// `Pool` accumulators when the position was last minted, one per reward token.
+    paid_acc_reward_per_share: Vec<u128>,
+    /// `Pool` accumulators at which the boost was settled, if it was.
+    settled_acc_reward_per_share: Option<Vec<u128>>,
+}
+
+impl Position {
+    fn boosted_supply(&self) -> u64 {
+        let multiplier_bps = if self.settled_acc_reward_per_share.is_some() {
+            MAX_PERCENTAGE
+        } else {
+            self.multiplier_bps
+        };
+
+        self.amount * multiplier_bps / MAX_PERCENTAGE
+    }
+
+    /// The contract keeps the boost of a `Pool` nonce until it is settled or re-minted.
+    fn has_settleable_boost(&self, emission_mode: EmissionMode) -> bool {
+        emission_mode == EmissionMode::Pool
+            && self.multiplier_bps > MAX_PERCENTAGE
+            && self.settled_acc_reward_per_share.is_none()
+    }
+}
+
+struct RewardTokenModel {
+    token: TestTokenIdentifier<'static>,
+    start_ts_ms: u64,
+    end_ts_ms: u64,
+    reward_per_share: u64,
+    rates: Vec<(u64, u64)>,
+    reserve: u64,
+    acc_reward_per_share: u128,
+    last_reward_ts_ms: u64,
+    paid: [u64; USERS.len()],
+    unpaid: [u64; USERS.len()],
+}
+
+impl RewardTokenModel {
+    fn new(
+        token: TestTokenIdentifier<'static>,
+        (start_ts_ms, end_ts_ms): (u64, u64),
+        reward_per_sec: u64,
+        reward_per_share: u64,
+        reserve: u64,
+    ) -> Self {
+        RewardTokenModel {
+            token,
+            start_ts_ms,
+            end_ts_ms,
+            reward_per_share,
+            rates: vec![(DEPLOY_TS_MS, reward_per_sec)],
+            reserve,
+            acc_reward_per_share: 0,
+            last_reward_ts_ms: 0,
+            paid: [0; USERS.len()],
+            unpaid: [0; USERS.len()],
+        }
+    }
+
+    fn reward_per_sec(&self) -> u64 {
+        self.rates.last().unwrap().1
+    }
+
+    fn rewarded_ms(&self, from_ms: u64, to_ms: u64) -> u64 {
+        to_ms
+            .min(self.end_ts_ms)
+            .saturating_sub(from_ms.max(self.start_ts_ms))
+    }
+
+    /// Integral of the `FixedRate` emission between two timestamps, in reward units times milliseconds.
+    fn emitted(&self, from_ms: u64, to_ms: u64) -> u128 {
+        let mut emitted = 0u128;
+        for (index, &(rate_start_ms, reward_per_sec)) in self.rates.iter().enumerate() {
+            let rate_end_ms = self
+                .rates
+                .get(index + 1)
+                .map_or(u64::MAX, |&(next_start_ms, _)| next_start_ms);
+            let rewarded_ms = self.rewarded_ms(from_ms.max(rate_start_ms), to_ms.min(rate_end_ms));
+            emitted += reward_per_sec as u128 * rewarded_ms as u128;
+        }
+
+        emitted
+    }
+}
+
+/// Reference model of a farm.
+///
+/// `FixedRate` emission is integrated analytically: the accumulator is exact, so the rewards of
+/// a position are `amount * multiplier * integral(reward_per_sec) / reward_per_share`, floored,
+/// with the boost ending with the lock. `Pool` emission is replayed update by update over the
+/// boosted supply, rounding down as the contract does.
+struct Model {
+    emission_mode: EmissionMode,
+    now_ms: u64,
+    reward_tokens: Vec<RewardTokenModel>,
+    positions: Vec<Position>,
+    last_nonce: u64,
+    boosted_supply: u64,
+}
+
+impl Model {
+    fn new(emission_mode: EmissionMode, now_ms: u64, reward_tokens: Vec<RewardTokenModel>) -> Self {
+        Model {
+            emission_mode,
+            now_ms,
+            reward_tokens,
+            positions: Vec::new(),
+            last_nonce: 0,
+            boosted_supply: 0,
+        }
+    }
+
+    /// `Pool` accumulators as the next update brings them to `now_ms`.
+    fn pending_acc_reward_per_share(&self) -> Vec<u128> {
+        self.reward_tokens
+            .iter()
+            .map(|reward_token| {
+                if reward_token.last_reward_ts_ms >= self.now_ms || self.boosted_supply == 0 {
+                    return reward_token.acc_reward_per_share;
+                }
+
+                let rewarded_ms =
+                    reward_token.rewarded_ms(reward_token.last_reward_ts_ms, self.now_ms);
+                reward_token.acc_reward_per_share
+                    + reward_token.reward_per_sec() as u128 * rewarded_ms as u128 * WAD as u128
+                        / (self.boosted_supply as u128 * 1_000)
+            })
+            .collect()
+    }
+
+    /// Mirrors the accumulator update every state changing endpoint starts with.
+    fn update(&mut self) {
+        if self.emission_mode != EmissionMode::Pool {
+            return;
+        }
+
+        let pending = self.pending_acc_reward_per_share();
+        for (reward_token, acc_reward_per_share) in self.reward_tokens.iter_mut().zip(pending) {
+            if reward_token.last_reward_ts_ms < self.now_ms {
+                reward_token.acc_reward_per_share = acc_reward_per_share;
+                reward_token.last_reward_ts_ms = self.now_ms;
+            }
+        }
+    }
+
+    fn rewards(&self, position: &Position) -> Vec<u64> {
+        let pending = self.pending_acc_reward_per_share();
+        let scale = |delta: u128, multiplier_bps: u64, divisor: u128| {
+            (delta * position.amount as u128 * multiplier_bps as u128
+                / (divisor * MAX_PERCENTAGE as u128)) as u64
+        };
+
+        self.reward_tokens
+            .iter()
+            .enumerate()
+            .map(|(index, reward_token)| match self.emission_mode {
+                EmissionMode::FixedRate => {
+                    let divisor = reward_token.reward_per_share as u128 * 1_000;
+                    let boost_end_ms = position
+                        .lock_until_ts_ms
+                        .max(position.update_ts_ms)
+                        .min(self.now_ms);
+                    scale(
+                        reward_token.emitted(position.update_ts_ms, boost_end_ms),
+                        position.multiplier_bps,
+                        divisor,
+                    ) + scale(
+                        reward_token.emitted(boost_end_ms, self.now_ms),
+                        MAX_PERCENTAGE,
+                        divisor,
+                    )
+                }
+                EmissionMode::Pool => {
+                    let paid = position.paid_acc_reward_per_share[index];
+                    let boost_end = position
+                        .settled_acc_reward_per_share
+                        .as_ref()
+                        .map_or(pending[index], |settled| settled[index]);
+                    scale(
+                        boost_end.saturating_sub(paid),
+                        position.multiplier_bps,
+                        WAD as u128,
+                    ) + scale(
+                        pending[index].saturating_sub(boost_end.max(paid)),
+                        MAX_PERCENTAGE,
+                        WAD as u128,
+                    )
+                }
+            })
+            .collect()
+    }
+
+    fn current_acc_reward_per_share(&self) -> Vec<u128> {
+        self.reward_tokens
+            .iter()
+            .map(|reward_token| reward_token.acc_reward_per_share)
+            .collect()
+    }
+
+    /// Error expected when claiming the rewards of a position, if any.
+    fn claim_error(&self, rewards: &[u64]) -> Option<&'static [u8]> {
+        if rewards.iter().all(|&reward| reward == 0) {
+            Some(ERROR_NO_REWARDS_APPLICABLE)
+        } else {
+            None
+        }
+    }
+
+    /// Pays what the reserves hold, the remainder being owed to the user.
+    fn pay(&mut self, user: usize, rewards: &[u64]) {
+        for (reward_token, &reward) in self.reward_tokens.iter_mut().zip(rewards) {
+            let paid = reward.min(reward_token.reserve);
+            reward_token.paid[user] += paid;
+            reward_token.unpaid[user] += reward - paid;
+            reward_token.reserve -= paid;
+        }
+    }
+
+    /// Updates a position in place, as claiming, merging or partially unstaking its whole nonce does.
+    fn remint(
+        &mut self,
+        position_index: usize,
+        amount: u64,
+        lock_until_ts_ms: u64,
+        multiplier_bps: u64,
+    ) {
+        let acc_reward_per_share = self.current_acc_reward_per_share();
+        let now_ms = self.now_ms;
+
+        let position = &mut self.positions[position_index];
+        self.boosted_supply -= position.boosted_supply();
+        position.amount = amount;
+        position.update_ts_ms = now_ms;
+        position.lock_until_ts_ms = lock_until_ts_ms;
+        position.multiplier_bps = multiplier_bps;
+        position.paid_acc_reward_per_share = acc_reward_per_share;
+        position.settled_acc_reward_per_share = None;
+        self.boosted_supply += position.boosted_supply();
+
+        if amount == 0 {
+            self.positions.remove(position_index);
+        }
+    }
+
+    /// Multiplier kept by a position re-minted now: the boost is dropped once the lock ended.
+    fn remaining_multiplier_bps(&self, position: &Position) -> u64 {
+        if position.lock_until_ts_ms > self.now_ms {
+            position.multiplier_bps
+        } else {
+            MAX_PERCENTAGE
+        }
+    }
+
+    fn user_position(&self, user: usize, index: usize) -> Option<usize> {
+        let owned: Vec<usize> = (0..self.positions.len())
+            .filter(|&position_index| self.positions[position_index].user == user)
+            .collect();
+        if owned.is_empty() {
+            return None;
+        }
+
+        Some(owned[index % owned.len()])
+    }
+
+    fn reward_token_index(&self, token: usize) -> usize {
+        token % self.reward_tokens.len()
+    }
+
+    fn staked(&self) -> u64 {
+        self.positions.iter().map(|position| position.amount).sum()
+    }
+
+    fn user_staked(&self, user: usize) -> u64 {
+        self.positions
+            .iter()
+            .filter(|position| position.user == user)
+            .map(|position| position.amount)
+            .sum()
+    }
+}
+
+fn new_state(
+    emission_mode: EmissionMode,
+    initial_ts_ms: u64,
+    initial_reserve: u64,
+    opt_second_reserve: Option<u64>,
+) -> TokenStakingTestState {
+    let mut state = TokenStakingTestState::new_with_mode(emission_mode);
+
+    for user in &USERS[2..] {
+        state
+            .world
+            .account(*user)
+            .nonce(1)
+            .esdt_balance(STAKED_TOKEN, INITIAL_STAKED_BALANCE);
+    }
+    state.set_lock_tier(LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS);
+    if let Some(second_reserve) = opt_second_reserve {
+        state.add_reward_token(
+            SECOND_REWARD_TOKEN,
+            SECOND_START_TS_MS,
+            SECOND_END_TS_MS,
+            SECOND_REWARD_PER_SEC,
+            SECOND_REWARD_PER_SHARE,
+        );
+        if second_reserve > 0 {
+            state.deposit_reward_token(OWNER, SECOND_REWARD_TOKEN, second_reserve);
+        }
+    }
+    if initial_reserve < INITIAL_REWARD_RESERVE {
+        state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE - initial_reserve);
+    }
+    state.set_block_timestamp_ms(initial_ts_ms);
+
+    state
+}
+
+fn new_model(
+    emission_mode: EmissionMode,
+    initial_ts_ms: u64,
+    initial_reserve: u64,
+    opt_second_reserve: Option<u64>,
+) -> Model {
+    let mut reward_tokens = vec![RewardTokenModel::new(
+        REWARD_TOKEN,
+        (START_TS_MS, END_TS_MS),
+        REWARD_PER_SEC,
+        REWARD_PER_SHARE,
+        initial_reserve,
+    )];
+    if let Some(second_reserve) = opt_second_reserve {
+        reward_tokens.push(RewardTokenModel::new(
+            SECOND_REWARD_TOKEN,
+            (SECOND_START_TS_MS, SECOND_END_TS_MS),
+            SECOND_REWARD_PER_SEC,
+            SECOND_REWARD_PER_SHARE,
+            second_reserve,
+        ));
+    }
+
+    Model::new(emission_mode, initial_ts_ms, reward_tokens)
+}
+
+fn apply(state: &mut TokenStakingTestState, model: &mut Model, action: &Action) {
+    match *action {
+        Action::Stake {
+            user,
+            amount,
+            merge,
+            locked,
+        } => match model.user_position(user, 0).filter(|_| merge && !locked) {
+            Some(position_index) => {
+                let position = &model.positions[position_index];
+                let (nonce, share_amount) = (position.nonce, position.amount);
+                let lock_until_ts_ms = position.lock_until_ts_ms;
+                let position_multiplier_bps = model.remaining_multiplier_bps(position);
+                let multiplier_bps = (amount * MAX_PERCENTAGE
+                    + share_amount * position_multiplier_bps)
+                    / (amount + share_amount);
+
+                state.stake_and_merge(USERS[user], amount, nonce, share_amount);
+                model.update();
+                let rewards = model.rewards(&model.positions[position_index]);
+                model.pay(user, &rewards);
+                model.remint(
+                    position_index,
+                    share_amount + amount,
+                    lock_until_ts_ms,
+                    multiplier_bps,
+                );
+            }
+            None => {
+                let (lock_until_ts_ms, multiplier_bps) = if locked {
+                    state.stake_locked(USERS[user], amount, LOCK_PERIOD_MS);
+                    (model.now_ms + LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS)
+                } else {
+                    state.stake(USERS[user], amount);
+                    (0, MAX_PERCENTAGE)
+                };
+                model.update();
+                model.last_nonce += 1;
+                model.positions.push(Position {
+                    user,
+                    nonce: model.last_nonce,
+                    amount,
+                    update_ts_ms: model.now_ms,
+                    lock_until_ts_ms,
+                    multiplier_bps,
+                    paid_acc_reward_per_share: model.current_acc_reward_per_share(),
+                    settled_acc_reward_per_share: None,
+                });
+                model.boosted_supply += amount * multiplier_bps / MAX_PERCENTAGE;
+            }
+        },
+        Action::Claim { user, index } => {
+            let Some(position_index) = model.user_position(user, index) else {
+                return;
+            };
+            let position = &model.positions[position_index];
+            let rewards = model.rewards(position);
+            let (nonce, share_amount) = (position.nonce, position.amount);
+            match model.claim_error(&rewards) {
+                Some(error) => {
+                    state.claim_rewards_expect_error(USERS[user], nonce, share_amount, error)
+                }
+                None => {
+                    state.claim_rewards(USERS[user], nonce, share_amount);
+                    model.update();
+                    model.pay(user, &rewards);
+                    let position = &model.positions[position_index];
+                    let lock_until_ts_ms = position.lock_until_ts_ms;
+                    let multiplier_bps = model.remaining_multiplier_bps(position);
+                    model.remint(
+                        position_index,
+                        share_amount,
+                        lock_until_ts_ms,
+                        multiplier_bps,
+                    );
+                }
+            }
+        }
+        Action::Unstake {
+            user,
+            index,
+            percent,
+        } => {
+            let Some(position_index) = model.user_position(user, index) else {
+                return;
+            };
+            let position = &model.positions[position_index];
+            let (nonce, share_amount) = (position.nonce, position.amount);
+            if position.lock_until_ts_ms > model.now_ms {
+                state.unstake_expect_error(USERS[user], nonce, share_amount, ERROR_POSITION_LOCKED);
+                return;
+            }
+
+            let rewards = model.rewards(position);
+            let unstake_amount = (share_amount * percent / 100).max(1);
+            state.unstake_partial(USERS[user], nonce, share_amount, unstake_amount);
+            model.update();
+            model.pay(user, &rewards);
+            let lock_until_ts_ms = model.positions[position_index].lock_until_ts_ms;
+            model.remint(
+                position_index,
+                share_amount - unstake_amount,
+                lock_until_ts_ms,
+                MAX_PERCENTAGE,
+            );
+        }
+        Action::SettleBoost { index } => {
+            if model.positions.is_empty() {
+                return;
+            }
+
+            let position_index = index % model.positions.len();
+            let position = &model.positions[position_index];
+            if !position.has_settleable_boost(model.emission_mode)
+                || position.lock_until_ts_ms > model.now_ms
+            {
+                state.settle_expired_boost_expect_error(
+                    USER,
+                    position.nonce,
+                    ERROR_NO_EXPIRED_BOOST,
+                );
+                return;
+            }
+
+            state.settle_expired_boost(USER, position.nonce);
+            model.update();
+            let acc_reward_per_share = model.current_acc_reward_per_share();
+            let position = &mut model.positions[position_index];
+            let boosted_supply = position.boosted_supply();
+            position.settled_acc_reward_per_share = Some(acc_reward_per_share);
+            model.boosted_supply -= boosted_supply - position.boosted_supply();
+        }
+        Action::ClaimUnpaid { user } => {
+            let owed = model
+                .reward_tokens
+                .iter()
+                .any(|reward_token| reward_token.unpaid[user] > 0);
+            let payable = model
+                .reward_tokens
+                .iter()
+                .any(|reward_token| reward_token.unpaid[user] > 0 && reward_token.reserve > 0);
+            if !owed {
+                state.claim_unpaid_rewards_expect_error(USERS[user], ERROR_NO_UNPAID_REWARDS);
+            } else if !payable {
+                state.claim_unpaid_rewards_expect_error(USERS[user], ERROR_NOT_ENOUGH_REWARDS);
+            } else {
+                state.claim_unpaid_rewards(USERS[user]);
+                for reward_token in model.reward_tokens.iter_mut() {
+                    let paid = reward_token.unpaid[user].min(reward_token.reserve);
+                    reward_token.paid[user] += paid;
+                    reward_token.unpaid[user] -= paid;
+                    reward_token.reserve -= paid;
+                }
+            }
+        }
+        Action::ModifyRewards {
+            token,
+            reward_per_sec,
+        } => {
+            let token_index = model.reward_token_index(token);
+            let reward_token = &model.reward_tokens[token_index];
+            if model.now_ms >= reward_token.end_ts_ms {
+                return;
+            }
+
+            state.modify_reward_token_rewards(
+                reward_token.token,
+                reward_per_sec,
+                reward_token.reward_per_share,
+            );
+            model.update();
+            let now_ms = model.now_ms;
+            let rates = &mut model.reward_tokens[token_index].rates;
+            if rates.last().unwrap().0 == now_ms {
+                rates.pop();
+            }
+            rates.push((now_ms, reward_per_sec));
+        }
+        Action::Deposit { token, amount } => {
+            let token_index = model.reward_token_index(token);
+            let reward_token = &mut model.reward_tokens[token_index];
+            state.deposit_reward_token(OWNER, reward_token.token, amount);
+            reward_token.reserve += amount;
+        }
+        Action::Withdraw { token, percent } => {
+            let token_index = model.reward_token_index(token);
+            let reward_token = &mut model.reward_tokens[token_index];
+            if reward_token.reserve == 0 {
+                return;
+            }
+
+            let amount = (reward_token.reserve * percent / 100).max(1);
+            state.withdraw_reward_token(reward_token.token, amount);
+            reward_token.reserve -= amount;
+        }
+        Action::AdvanceTime { seconds } => {
+            model.now_ms += seconds * 1_000;
+            state.set_block_timestamp_ms(model.now_ms);
+        }
+    }
+}
+
+fn check_invariants(state: &mut TokenStakingTestState, model: &Model) {
+    let staked = model.staked();
+    let boosted_supply = model.boosted_supply;
+    let user_staked: Vec<u64> = (0..USERS.len())
+        .map(|user| model.user_staked(user))
+        .collect();
+    let reward_tokens = &model.reward_tokens;
+
+    state
+        .world
+        .query()
+        .to(SC)
+        .whitebox(tokenstaking::contract_obj, |sc| {
+            let staked_token = EgldOrEsdtTokenIdentifier::esdt(STAKED_TOKEN.to_token_identifier());
+
+            let staked_balance = sc.blockchain().get_sc_balance(&staked_token, 0);
+            assert!(staked_balance >= staked, "staked balance below positions");
+            assert_eq!(sc.total_staked(FARM_ID).get(), staked);
+            assert_eq!(sc.total_share_supply(FARM_ID).get(), staked);
+            assert_eq!(sc.boosted_share_supply(FARM_ID).get(), boosted_supply);
+
+            for reward_token_model in reward_tokens {
+                let token = reward_token_model.token;
+                let reward_token = EgldOrEsdtTokenIdentifier::esdt(token.to_token_identifier());
+                let rewards_reserve = sc.rewards_reserve(FARM_ID, &reward_token).get();
+                assert_eq!(
+                    rewards_reserve, reward_token_model.reserve,
+                    "reserve of {token:?}"
+                );
+                assert!(sc.blockchain().get_sc_balance(&reward_token, 0) >= rewards_reserve);
+
+                for (user, address) in USERS.iter().enumerate() {
+                    let rewards_received = sc
+                        .blockchain()
+                        .get_esdt_token_data(
+                            &address.to_managed_address(),
+                            &token.to_token_identifier(),
+                            0,
+                        )
+                        .amount;
+                    assert_eq!(
+                        rewards_received, reward_token_model.paid[user],
+                        "{token:?} paid to user {user}"
+                    );
+                    assert_eq!(
+                        sc.unpaid_rewards(FARM_ID, &address.to_managed_address())
+                            .get(&reward_token)
+                            .unwrap_or_default(),
+                        reward_token_model.unpaid[user],
+                        "{token:?} owed to user {user}"
+                    );
+                }
+            }
+
+            for (user, address) in USERS.iter().enumerate() {
+                assert_eq!(
+                    sc.user_staked(FARM_ID, &address.to_managed_address()).get(),
+                    user_staked[user]
+                );
+            }
+        });
+
+    for position in &model.positions {
+        assert_eq!(
+            state.get_share_token_balance(USERS[position.user], position.nonce),
+            position.amount
+        );
+        assert_eq!(
+            state.get_share_token_lock(USERS[position.user], position.nonce),
+            (position.lock_until_ts_ms, position.multiplier_bps)
+        );
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn reward_accounting_invariants_hold(
+        pool in any::<bool>(),
+        initial_seconds in (DEPLOY_TS_MS / 1_000)..(END_TS_MS / 1_000),
+        initial_reserve in 0..=MAX_INITIAL_RESERVE,
+        opt_second_reserve in prop::option::of(0..=MAX_INITIAL_RESERVE),
+        actions in prop::collection::vec(action_strategy(), 1..40),
+    ) {
+        let emission_mode = if pool { EmissionMode::Pool } else { EmissionMode::FixedRate };
+        let initial_ts_ms = initial_seconds * 1_000;
+        let mut state = new_state(emission_mode, initial_ts_ms, initial_reserve, opt_second_reserve);
+        let mut model = new_model(emission_mode, initial_ts_ms, initial_reserve, opt_second_reserve);
+
+        for action in &actions {
+            apply(&mut state, &mut model, action);
+            check_invariants(&mut state, &model);
+        }
+    }
+}
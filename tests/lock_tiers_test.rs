@@ -0,0 +1,193 @@
+mod setup;
+
+use constants::{MAX_LOCK_MULTIPLIER_BPS, MAX_PERCENTAGE};
+use errors::{
+    ERROR_LOCK_TIER_NOT_FOUND, ERROR_NO_EXPIRED_BOOST, ERROR_POSITION_LOCKED,
+    ERROR_WRONG_MULTIPLIER,
+};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::EmissionMode;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const LOCK_PERIOD_MS: u64 = 150_000;
+const LOCK_MULTIPLIER_BPS: u64 = 15_000;
+
+#[test]
+fn locked_stake_earns_boosted_rewards() {
+    let mut state = TokenStakingTestState::new();
+    state.set_lock_tier(LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS);
+
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_share_token_lock(USER, 1),
+        (DEPLOY_TS_MS + LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS)
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.claim_rewards(OTHER_USER, 2, STAKE_AMOUNT);
+
+    state.check_reward_balance(
+        USER,
+        expected_rewards(STAKE_AMOUNT, 20) * LOCK_MULTIPLIER_BPS / MAX_PERCENTAGE,
+    );
+    state.check_reward_balance(OTHER_USER, expected_rewards(STAKE_AMOUNT, 20));
+    assert_eq!(
+        state.get_share_token_lock(USER, 1),
+        (DEPLOY_TS_MS + LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS)
+    );
+}
+
+#[test]
+fn locked_stake_cannot_be_unstaked_before_lock_ends() {
+    let mut state = TokenStakingTestState::new();
+    state.set_lock_tier(LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS);
+
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + LOCK_PERIOD_MS - 1);
+    state.unstake_expect_error(USER, 1, STAKE_AMOUNT, ERROR_POSITION_LOCKED);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + LOCK_PERIOD_MS);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+}
+
+#[test]
+fn pool_rewards_are_shared_by_boosted_supply() {
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+    state.set_lock_tier(LOCK_PERIOD_MS * 2, MAX_PERCENTAGE * 2);
+
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS * 2);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 90_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.claim_rewards(OTHER_USER, 2, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, REWARD_PER_SEC * 90 * 2 / 3);
+    state.check_reward_balance(OTHER_USER, REWARD_PER_SEC * 90 / 3);
+}
+
+#[test]
+fn boost_is_dropped_when_reminted_after_lock_ends() {
+    let mut state = TokenStakingTestState::new();
+    state.set_lock_tier(LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS);
+
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + LOCK_PERIOD_MS + 10_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+
+    // boosted from the start until the lock ends, then at the base rate
+    let locked_seconds = (DEPLOY_TS_MS + LOCK_PERIOD_MS - START_TS_MS) / 1_000;
+    state.check_reward_balance(
+        USER,
+        expected_rewards(STAKE_AMOUNT, locked_seconds) * LOCK_MULTIPLIER_BPS / MAX_PERCENTAGE
+            + expected_rewards(STAKE_AMOUNT, 10),
+    );
+    assert_eq!(state.get_accrued_rewards(), 0u64.into());
+    assert_eq!(
+        state.get_share_token_lock(USER, 1),
+        (DEPLOY_TS_MS + LOCK_PERIOD_MS, MAX_PERCENTAGE)
+    );
+}
+
+#[test]
+fn pool_boost_stops_diluting_once_settled_after_lock_ends() {
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+    state.set_lock_tier(LOCK_PERIOD_MS, MAX_PERCENTAGE * 2);
+
+    state.stake_locked(USER, STAKE_AMOUNT, LOCK_PERIOD_MS);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + LOCK_PERIOD_MS - 1);
+    state.settle_expired_boost_expect_error(OTHER_USER, 1, ERROR_NO_EXPIRED_BOOST);
+    state.settle_expired_boost_expect_error(OTHER_USER, 2, ERROR_NO_EXPIRED_BOOST);
+
+    // boosted until the settlement, then both positions share the pool equally
+    state.set_block_timestamp_ms(START_TS_MS + 60_000);
+    state.settle_expired_boost(OTHER_USER, 1);
+    state.settle_expired_boost_expect_error(OTHER_USER, 1, ERROR_NO_EXPIRED_BOOST);
+
+    state.set_block_timestamp_ms(START_TS_MS + 80_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.claim_rewards(OTHER_USER, 2, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, REWARD_PER_SEC * 60 * 2 / 3 + REWARD_PER_SEC * 20 / 2);
+    state.check_reward_balance(
+        OTHER_USER,
+        REWARD_PER_SEC * 60 / 3 + REWARD_PER_SEC * 20 / 2,
+    );
+    assert_eq!(
+        state.get_share_token_lock(USER, 1),
+        (DEPLOY_TS_MS + LOCK_PERIOD_MS, MAX_PERCENTAGE)
+    );
+
+    state.unstake(USER, 1, STAKE_AMOUNT);
+    state.unstake(OTHER_USER, 2, STAKE_AMOUNT);
+    assert_eq!(state.get_boosted_share_supply(), 0u64.into());
+}
+
+#[test]
+fn merged_positions_keep_latest_lock_and_weighted_boost() {
+    let mut state = TokenStakingTestState::new();
+    state.set_lock_tier(LOCK_PERIOD_MS, LOCK_MULTIPLIER_BPS);
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .stake(FARM_ID, OptionalValue::Some(LOCK_PERIOD_MS))
+        .esdt(TestEsdtTransfer(STAKED_TOKEN, 0, STAKE_AMOUNT))
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 1, STAKE_AMOUNT))
+        .run();
+
+    assert_eq!(
+        state.get_share_token_lock(USER, 1),
+        (
+            START_TS_MS + 10_000 + LOCK_PERIOD_MS,
+            (LOCK_MULTIPLIER_BPS + MAX_PERCENTAGE) / 2
+        )
+    );
+}
+
+#[test]
+fn invalid_lock_tiers_are_rejected() {
+    let mut state = TokenStakingTestState::new();
+
+    for multiplier_bps in [MAX_PERCENTAGE - 1, MAX_LOCK_MULTIPLIER_BPS + 1] {
+        state
+            .world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_lock_tier(FARM_ID, LOCK_PERIOD_MS, multiplier_bps)
+            .with_result(ExpectError(4, as_str(ERROR_WRONG_MULTIPLIER)))
+            .run();
+    }
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .stake(FARM_ID, OptionalValue::Some(LOCK_PERIOD_MS))
+        .single_esdt(
+            &STAKED_TOKEN.to_token_identifier(),
+            0,
+            &BigUint::from(STAKE_AMOUNT),
+        )
+        .with_result(ExpectError(4, as_str(ERROR_LOCK_TIER_NOT_FOUND)))
+        .run();
+}
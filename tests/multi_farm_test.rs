@@ -0,0 +1,178 @@
+mod setup;
+
+use constants::{LEGACY_STAKED_TOKEN_KEY, MAX_PERCENTAGE};
+use errors::{ERROR_FARM_NOT_FOUND, ERROR_INVALID_SHARE_TOKEN, ERROR_LEGACY_STORAGE_LAYOUT};
+use multiversx_sc::storage::StorageKey;
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::EmissionMode;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const SECOND_FARM_ID: u64 = 2;
+const SECOND_REWARD_PER_SEC: u64 = 4;
+
+fn new_state_with_second_farm() -> TokenStakingTestState {
+    let mut state = TokenStakingTestState::new_for_second_farm();
+
+    state.create_farm_with_tokens(
+        SECOND_STAKED_TOKEN,
+        SECOND_REWARD_TOKEN,
+        SECOND_SHARE_TOKEN,
+        START_TS_MS,
+        END_TS_MS,
+        SECOND_REWARD_PER_SEC,
+        REWARD_PER_SHARE,
+        EmissionMode::Pool,
+    );
+    state.deposit_rewards_in_farm(
+        OWNER,
+        SECOND_FARM_ID,
+        SECOND_REWARD_TOKEN,
+        INITIAL_REWARD_RESERVE,
+    );
+
+    state
+}
+
+#[test]
+fn farms_are_accounted_independently() {
+    let mut state = new_state_with_second_farm();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake_in_farm(USER, SECOND_FARM_ID, SECOND_STAKED_TOKEN, STAKE_AMOUNT * 3);
+    state.stake_in_farm(
+        OTHER_USER,
+        SECOND_FARM_ID,
+        SECOND_STAKED_TOKEN,
+        STAKE_AMOUNT,
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 100_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+    state.unstake_share_token(USER, SECOND_SHARE_TOKEN, 1, STAKE_AMOUNT * 3);
+
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 100));
+    state.check_reward_token_balance(
+        USER,
+        SECOND_REWARD_TOKEN,
+        SECOND_REWARD_PER_SEC * 100 * 3 / 4,
+    );
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+
+    let farms = state.get_farms(0, 10);
+    assert_eq!(farms.len(), 2);
+    assert_eq!(farms[0].total_staked, 0u64);
+    assert_eq!(farms[1].farm_id, SECOND_FARM_ID);
+    assert_eq!(farms[1].total_staked, STAKE_AMOUNT);
+    assert_eq!(farms[1].emission_mode, EmissionMode::Pool);
+}
+
+#[test]
+fn farms_can_share_a_staked_token() {
+    let mut state = TokenStakingTestState::new_for_second_farm();
+
+    state.create_farm_with_tokens(
+        STAKED_TOKEN,
+        SECOND_REWARD_TOKEN,
+        SECOND_SHARE_TOKEN,
+        START_TS_MS,
+        END_TS_MS,
+        REWARD_PER_SEC,
+        REWARD_PER_SHARE,
+        EmissionMode::FixedRate,
+    );
+
+    state.stake_in_farm(USER, SECOND_FARM_ID, STAKED_TOKEN, STAKE_AMOUNT);
+
+    assert_eq!(state.get_total_staked(), 0u64.into());
+    state
+        .world
+        .check_account(USER)
+        .esdt_nft_balance_and_attributes(
+            SECOND_SHARE_TOKEN,
+            1,
+            STAKE_AMOUNT,
+            structs::ShareTokenAttributes::<StaticApi> {
+                update_ts_ms: DEPLOY_TS_MS,
+                acc_reward_per_share: ManagedVec::from_single_item(BigUint::zero()),
+                lock_until_ts_ms: 0,
+                multiplier_bps: MAX_PERCENTAGE,
+            },
+        );
+}
+
+#[test]
+fn share_tokens_of_different_farms_cannot_be_merged() {
+    let mut state = new_state_with_second_farm();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake_in_farm(USER, SECOND_FARM_ID, SECOND_STAKED_TOKEN, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .claim_rewards_endpoint(OptionalValue::<bool>::None)
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 1, STAKE_AMOUNT))
+        .esdt(TestEsdtTransfer(SECOND_SHARE_TOKEN, 1, STAKE_AMOUNT))
+        .with_result(ExpectError(4, as_str(ERROR_INVALID_SHARE_TOKEN)))
+        .run();
+}
+
+#[test]
+fn farms_are_listed_with_pagination() {
+    let mut state = new_state_with_second_farm();
+
+    assert_eq!(state.get_farms(0, 1).len(), 1);
+    assert_eq!(state.get_farms(1, 10)[0].farm_id, SECOND_FARM_ID);
+    assert!(state.get_farms(2, 10).is_empty());
+}
+
+#[test]
+fn unknown_farm_is_rejected() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake_in_farm_expect_error(USER, 3, STAKED_TOKEN, STAKE_AMOUNT, ERROR_FARM_NOT_FOUND);
+}
+
+#[test]
+fn single_farm_deployments_cannot_be_upgraded_in_place() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .upgrade()
+        .code(CODE_PATH)
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .whitebox(tokenstaking::contract_obj, |_| {
+            SingleValueMapper::<DebugApi, EgldOrEsdtTokenIdentifier<DebugApi>>::new(
+                StorageKey::new(LEGACY_STAKED_TOKEN_KEY),
+            )
+            .set(EgldOrEsdtTokenIdentifier::esdt(STAKED_TOKEN));
+        });
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .upgrade()
+        .code(CODE_PATH)
+        .with_result(ExpectError(4, as_str(ERROR_LEGACY_STORAGE_LAYOUT)))
+        .run();
+}
@@ -0,0 +1,145 @@
+mod setup;
+
+use errors::{ERROR_REWARD_TOKEN_ALREADY_ADDED, ERROR_WRONG_START_TS};
+use setup::*;
+use structs::EmissionMode;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const SECOND_START_TS_MS: u64 = START_TS_MS + 50_000;
+const SECOND_END_TS_MS: u64 = END_TS_MS + 100_000;
+const SECOND_REWARD_PER_SEC: u64 = 5;
+const SECOND_REWARD_PER_SHARE: u64 = 100;
+
+fn new_state_with_second_reward(emission_mode: EmissionMode) -> TokenStakingTestState {
+    let mut state = TokenStakingTestState::new_with_mode(emission_mode);
+
+    state.add_reward_token(
+        SECOND_REWARD_TOKEN,
+        SECOND_START_TS_MS,
+        SECOND_END_TS_MS,
+        SECOND_REWARD_PER_SEC,
+        SECOND_REWARD_PER_SHARE,
+    );
+    state.deposit_reward_token(OWNER, SECOND_REWARD_TOKEN, INITIAL_REWARD_RESERVE);
+
+    state
+}
+
+#[test]
+fn reward_tokens_accrue_on_their_own_schedules() {
+    let mut state = new_state_with_second_reward(EmissionMode::FixedRate);
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 80_000);
+    let second_expected = SECOND_REWARD_PER_SEC * 30 * STAKE_AMOUNT / SECOND_REWARD_PER_SHARE;
+    assert_eq!(
+        state.get_claimable_reward_token(USER, 1, STAKE_AMOUNT, SECOND_REWARD_TOKEN),
+        second_expected.into()
+    );
+
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 80));
+    state.check_reward_token_balance(USER, SECOND_REWARD_TOKEN, second_expected);
+}
+
+#[test]
+fn claim_before_second_reward_starts_pays_only_accrued_token() {
+    let mut state = new_state_with_second_reward(EmissionMode::FixedRate);
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 10));
+    state.check_reward_token_balance(USER, SECOND_REWARD_TOKEN, 0);
+}
+
+#[test]
+fn reward_token_added_later_pays_existing_pool_positions() {
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.add_reward_token(
+        SECOND_REWARD_TOKEN,
+        SECOND_START_TS_MS,
+        SECOND_END_TS_MS,
+        SECOND_REWARD_PER_SEC,
+        SECOND_REWARD_PER_SHARE,
+    );
+    state.deposit_reward_token(OWNER, SECOND_REWARD_TOKEN, INITIAL_REWARD_RESERVE);
+
+    state.set_block_timestamp_ms(SECOND_START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, REWARD_PER_SEC * 70);
+    state.check_reward_token_balance(USER, SECOND_REWARD_TOKEN, SECOND_REWARD_PER_SEC * 20);
+}
+
+#[test]
+fn modifying_one_reward_token_leaves_the_others_unchanged() {
+    let mut state = new_state_with_second_reward(EmissionMode::FixedRate);
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(SECOND_START_TS_MS);
+    state.modify_reward_token_rewards(
+        SECOND_REWARD_TOKEN,
+        SECOND_REWARD_PER_SEC * 2,
+        SECOND_REWARD_PER_SHARE,
+    );
+
+    state.set_block_timestamp_ms(SECOND_START_TS_MS + 10_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        expected_rewards(STAKE_AMOUNT, 60).into()
+    );
+    assert_eq!(
+        state.get_claimable_reward_token(USER, 1, STAKE_AMOUNT, SECOND_REWARD_TOKEN),
+        (SECOND_REWARD_PER_SEC * 2 * 10 * STAKE_AMOUNT / SECOND_REWARD_PER_SHARE).into()
+    );
+}
+
+#[test]
+fn reward_tokens_are_listed_and_funded_separately() {
+    let mut state = new_state_with_second_reward(EmissionMode::FixedRate);
+
+    state.withdraw_reward_token(SECOND_REWARD_TOKEN, 1_000);
+
+    let farm = state.get_farm_info();
+    assert_eq!(farm.rewards.len(), 2);
+
+    let primary = farm.rewards.get(0);
+    assert_eq!(primary.rewards_reserve, INITIAL_REWARD_RESERVE);
+    assert_eq!(primary.start_ts_ms, START_TS_MS);
+
+    let second = farm.rewards.get(1);
+    assert_eq!(second.rewards_reserve, INITIAL_REWARD_RESERVE - 1_000);
+    assert_eq!(second.start_ts_ms, SECOND_START_TS_MS);
+    assert_eq!(second.end_ts_ms, SECOND_END_TS_MS);
+}
+
+#[test]
+fn reward_token_cannot_be_added_twice_or_in_the_past() {
+    let mut state = new_state_with_second_reward(EmissionMode::FixedRate);
+
+    state.add_reward_token_expect_error(
+        SECOND_REWARD_TOKEN,
+        SECOND_START_TS_MS,
+        SECOND_END_TS_MS,
+        ERROR_REWARD_TOKEN_ALREADY_ADDED,
+    );
+    state.add_reward_token_expect_error(
+        REWARD_TOKEN,
+        SECOND_START_TS_MS,
+        SECOND_END_TS_MS,
+        ERROR_REWARD_TOKEN_ALREADY_ADDED,
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS);
+    state.add_reward_token_expect_error(STAKED_TOKEN, START_TS_MS, END_TS_MS, ERROR_WRONG_START_TS);
+}
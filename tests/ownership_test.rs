@@ -0,0 +1,278 @@
+mod setup;
+
+use errors::{
+    ERROR_MISSING_ROLE, ERROR_NOT_ADMIN, ERROR_NOT_OWNER, ERROR_NOT_PENDING_OWNER,
+    ERROR_NO_PENDING_OWNER, ERROR_UPGRADE_RIGHTS_NOT_TRANSFERABLE,
+};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::Role;
+
+fn propose_owner(state: &mut TokenStakingTestState, from: TestAddress, new_owner: TestAddress) {
+    state
+        .world
+        .tx()
+        .from(from)
+        .to(SC)
+        .typed(TemplateProxy)
+        .propose_owner(new_owner.to_address())
+        .run();
+}
+
+fn accept_ownership_expect_error(state: &mut TokenStakingTestState, from: TestAddress) {
+    state
+        .world
+        .tx()
+        .from(from)
+        .to(SC)
+        .typed(TemplateProxy)
+        .accept_ownership()
+        .with_result(ExpectError(4, as_str(ERROR_NOT_PENDING_OWNER)))
+        .run();
+}
+
+fn make_contract_own_itself(state: &mut TokenStakingTestState) {
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(UserBuiltinProxy)
+        .change_owner_address(&SC.to_managed_address())
+        .run();
+}
+
+fn get_on_chain_owner(state: &mut TokenStakingTestState) -> Address {
+    let mut on_chain_owner = Address::zero();
+    state
+        .world
+        .query()
+        .to(SC)
+        .whitebox(tokenstaking::contract_obj, |sc| {
+            on_chain_owner = sc.blockchain().get_owner_address().to_address();
+        });
+
+    on_chain_owner
+}
+
+fn get_owner(state: &mut TokenStakingTestState) -> Address {
+    state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .get_owner()
+        .returns(ReturnsResultUnmanaged)
+        .run()
+}
+
+#[test]
+fn ownership_moves_once_accepted() {
+    let mut state = TokenStakingTestState::new();
+
+    accept_ownership_expect_error(&mut state, ADMIN);
+    propose_owner(&mut state, OWNER, USER);
+    propose_owner(&mut state, OWNER, ADMIN);
+    assert_eq!(get_owner(&mut state), OWNER.to_address());
+    assert_eq!(
+        state
+            .world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_pending_owner()
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .into_option(),
+        Some(ADMIN.to_address())
+    );
+
+    accept_ownership_expect_error(&mut state, USER);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .accept_ownership()
+        .with_result(ExpectError(
+            4,
+            as_str(ERROR_UPGRADE_RIGHTS_NOT_TRANSFERABLE),
+        ))
+        .run();
+    assert_eq!(get_owner(&mut state), OWNER.to_address());
+
+    make_contract_own_itself(&mut state);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .accept_ownership()
+        .run();
+    assert_eq!(get_owner(&mut state), ADMIN.to_address());
+    assert_eq!(get_on_chain_owner(&mut state), ADMIN.to_address());
+    assert!(state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .get_pending_owner()
+        .returns(ReturnsResultUnmanaged)
+        .run()
+        .is_none());
+    accept_ownership_expect_error(&mut state, ADMIN);
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .add_admin(MultiValueVec::from(vec![USER.to_address()]))
+        .with_result(ExpectError(4, as_str(ERROR_NOT_OWNER)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .add_admin(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .run();
+
+    state.withdraw_reward_token(REWARD_TOKEN, 1_000);
+    state
+        .world
+        .check_account(ADMIN)
+        .esdt_balance(REWARD_TOKEN, 1_000u64);
+}
+
+#[test]
+fn only_owner_proposes_owner() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .propose_owner(ADMIN.to_address())
+        .with_result(ExpectError(4, as_str(ERROR_NOT_OWNER)))
+        .run();
+}
+
+#[test]
+fn admin_renounces_admin_and_roles() {
+    let mut state = TokenStakingTestState::new();
+    state.add_admin(ADMIN);
+    state.grant_role(Role::Pauser, ADMIN);
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .renounce_admin()
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .renounce_admin()
+        .with_result(ExpectError(4, as_str(ERROR_NOT_ADMIN)))
+        .run();
+    state.pause(ADMIN);
+    state.unpause(ADMIN);
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .renounce_role(Role::Pauser)
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .renounce_role(Role::Pauser)
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .pause()
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+}
+
+#[test]
+fn upgrade_rights_follow_when_contract_owns_itself() {
+    let mut state = TokenStakingTestState::new();
+
+    make_contract_own_itself(&mut state);
+    assert_eq!(get_owner(&mut state), OWNER.to_address());
+
+    propose_owner(&mut state, OWNER, ADMIN);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .accept_ownership()
+        .run();
+
+    assert_eq!(get_owner(&mut state), ADMIN.to_address());
+    assert_eq!(get_on_chain_owner(&mut state), ADMIN.to_address());
+}
+
+#[test]
+fn owner_cancels_ownership_transfer() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .cancel_ownership_transfer()
+        .with_result(ExpectError(4, as_str(ERROR_NO_PENDING_OWNER)))
+        .run();
+
+    propose_owner(&mut state, OWNER, ADMIN);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .cancel_ownership_transfer()
+        .with_result(ExpectError(4, as_str(ERROR_NOT_OWNER)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .cancel_ownership_transfer()
+        .run();
+
+    make_contract_own_itself(&mut state);
+    accept_ownership_expect_error(&mut state, ADMIN);
+    assert_eq!(get_owner(&mut state), OWNER.to_address());
+}
@@ -0,0 +1,95 @@
+mod setup;
+
+use setup::*;
+use structs::EmissionMode;
+
+const ONE_HUNDRED_SECONDS_MS: u64 = 100_000;
+
+#[test]
+fn pool_emissions_do_not_scale_with_staked_supply() {
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+
+    state.stake(USER, 1_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + ONE_HUNDRED_SECONDS_MS);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, 1_000),
+        (REWARD_PER_SEC * 100).into()
+    );
+
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+
+    state.stake(USER, 1_000_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + ONE_HUNDRED_SECONDS_MS);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, 1_000_000),
+        (REWARD_PER_SEC * 100).into()
+    );
+}
+
+#[test]
+fn pool_rewards_are_shared_pro_rata() {
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+
+    state.stake(USER, 1_000);
+    state.stake(OTHER_USER, 3_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + ONE_HUNDRED_SECONDS_MS);
+    state.claim_rewards(USER, 1, 1_000);
+    state.claim_rewards(OTHER_USER, 2, 3_000);
+
+    state.check_reward_balance(USER, REWARD_PER_SEC * 100 / 4);
+    state.check_reward_balance(OTHER_USER, REWARD_PER_SEC * 100 * 3 / 4);
+}
+
+#[test]
+fn pool_late_staker_only_shares_later_emissions() {
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+
+    state.stake(USER, 1_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + ONE_HUNDRED_SECONDS_MS / 2);
+    state.stake(OTHER_USER, 1_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + ONE_HUNDRED_SECONDS_MS);
+    state.unstake(USER, 1, 1_000);
+    state.unstake(OTHER_USER, 2, 1_000);
+
+    state.check_reward_balance(USER, REWARD_PER_SEC * 50 + REWARD_PER_SEC * 50 / 2);
+    state.check_reward_balance(OTHER_USER, REWARD_PER_SEC * 50 / 2);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_staked_balance(OTHER_USER, INITIAL_STAKED_BALANCE);
+}
+
+#[test]
+fn pool_rate_change_applies_only_from_change() {
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+
+    state.stake(USER, 1_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + ONE_HUNDRED_SECONDS_MS / 2);
+    state.modify_rewards(REWARD_PER_SEC * 2, REWARD_PER_SHARE);
+
+    state.set_block_timestamp_ms(START_TS_MS + ONE_HUNDRED_SECONDS_MS);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, 1_000),
+        (REWARD_PER_SEC * 50 + REWARD_PER_SEC * 2 * 50).into()
+    );
+}
+
+#[test]
+fn pool_farm_does_not_require_reward_per_share() {
+    let mut state = TokenStakingTestState::deploy();
+
+    state.create_farm(
+        START_TS_MS,
+        END_TS_MS,
+        REWARD_PER_SEC,
+        0,
+        EmissionMode::Pool,
+    );
+
+    let farm = state.get_farm_info();
+    assert_eq!(farm.emission_mode, EmissionMode::Pool);
+}
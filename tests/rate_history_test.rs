@@ -0,0 +1,109 @@
+mod setup;
+
+use constants::WAD;
+use setup::*;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+fn rewards_at(amount: u64, reward_per_sec: u64, reward_per_share: u64, seconds: u64) -> u64 {
+    reward_per_sec * seconds * amount / reward_per_share
+}
+
+#[test]
+fn rate_cut_is_not_retroactive() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 50_000);
+    state.modify_rewards(REWARD_PER_SEC / 2, REWARD_PER_SHARE);
+
+    state.set_block_timestamp_ms(START_TS_MS + 100_000);
+    let expected = expected_rewards(STAKE_AMOUNT, 50)
+        + rewards_at(STAKE_AMOUNT, REWARD_PER_SEC / 2, REWARD_PER_SHARE, 50);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        expected.into()
+    );
+
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected);
+}
+
+#[test]
+fn rate_increase_is_not_retroactive() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 30_000);
+    state.modify_rewards(REWARD_PER_SEC * 3, REWARD_PER_SHARE * 2);
+
+    state.set_block_timestamp_ms(START_TS_MS + 100_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_reward_balance(
+        USER,
+        expected_rewards(STAKE_AMOUNT, 30)
+            + rewards_at(STAKE_AMOUNT, REWARD_PER_SEC * 3, REWARD_PER_SHARE * 2, 70),
+    );
+}
+
+#[test]
+fn rewards_are_integrated_across_several_changes() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.modify_rewards(4, 10);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 30_000);
+    state.modify_rewards(1, 10);
+
+    state.set_block_timestamp_ms(START_TS_MS + 40_000);
+    state.modify_rewards(6, 10);
+
+    state.set_block_timestamp_ms(START_TS_MS + 50_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        (rewards_at(STAKE_AMOUNT, REWARD_PER_SEC, REWARD_PER_SHARE, 10)
+            + rewards_at(STAKE_AMOUNT, 4, 10, 20)
+            + rewards_at(STAKE_AMOUNT, 1, 10, 10)
+            + rewards_at(STAKE_AMOUNT, 6, 10, 10))
+        .into()
+    );
+    assert_eq!(
+        state.get_claimable_rewards(OTHER_USER, 2, STAKE_AMOUNT),
+        (rewards_at(STAKE_AMOUNT, 4, 10, 10)
+            + rewards_at(STAKE_AMOUNT, 1, 10, 10)
+            + rewards_at(STAKE_AMOUNT, 6, 10, 10))
+        .into()
+    );
+}
+
+#[test]
+fn rate_history_records_checkpoints() {
+    let mut state = TokenStakingTestState::new();
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.modify_rewards(4, 10);
+    state.modify_rewards(5, 10);
+
+    let history = state.get_rate_history();
+    assert_eq!(history.len(), 2);
+
+    assert_eq!(history[0].start_ts_ms, DEPLOY_TS_MS);
+    assert_eq!(history[0].reward_per_sec, REWARD_PER_SEC);
+    assert_eq!(history[0].acc_reward_per_share, 0u64);
+
+    assert_eq!(history[1].start_ts_ms, START_TS_MS + 10_000);
+    assert_eq!(history[1].reward_per_sec, 5u64);
+    assert_eq!(
+        history[1].acc_reward_per_share,
+        REWARD_PER_SEC * 10 / REWARD_PER_SHARE * WAD
+    );
+}
@@ -0,0 +1,106 @@
+mod setup;
+
+use errors::{ERROR_INSUFFICIENT_RESERVE, ERROR_NOT_ENOUGH_REWARDS, ERROR_NO_UNPAID_REWARDS};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+#[test]
+fn unstake_returns_principal_and_defers_unpaid_rewards() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE - 100);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    let rewards = expected_rewards(STAKE_AMOUNT, 10);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_reward_balance(USER, 100);
+    assert_eq!(state.get_unpaid_rewards(USER), (rewards - 100).into());
+    assert_eq!(state.get_farm_info().rewards.get(0).rewards_reserve, 0u64);
+    assert_eq!(state.get_accrued_rewards(), (rewards - 100).into());
+
+    state.claim_unpaid_rewards_expect_error(USER, ERROR_NOT_ENOUGH_REWARDS);
+
+    state.deposit_rewards(OWNER, rewards / 2);
+    state.claim_unpaid_rewards(USER);
+    state.check_reward_balance(USER, 100 + rewards / 2);
+    assert_eq!(state.get_unpaid_rewards(USER), (rewards / 2 - 100).into());
+
+    state.deposit_rewards(OWNER, rewards);
+    state.claim_unpaid_rewards(USER);
+    state.check_reward_balance(USER, rewards);
+    assert_eq!(state.get_unpaid_rewards(USER), 0u64.into());
+    assert_eq!(state.get_accrued_rewards(), 0u64.into());
+
+    state.claim_unpaid_rewards_expect_error(USER, ERROR_NO_UNPAID_REWARDS);
+}
+
+#[test]
+fn claim_from_empty_reserve_keeps_position_and_defers_rewards() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+
+    state.check_share_token(USER, 1, STAKE_AMOUNT, START_TS_MS + 10_000);
+    assert_eq!(
+        state.get_unpaid_rewards(USER),
+        expected_rewards(STAKE_AMOUNT, 10).into()
+    );
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        0u64.into()
+    );
+}
+
+#[test]
+fn unpaid_rewards_belong_to_the_beneficiary() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.unstake_to(USER, OTHER_USER, 1, STAKE_AMOUNT, STAKE_AMOUNT);
+
+    assert_eq!(state.get_unpaid_rewards(USER), 0u64.into());
+    assert_eq!(
+        state.get_unpaid_rewards(OTHER_USER),
+        expected_rewards(STAKE_AMOUNT, 10).into()
+    );
+
+    state.deposit_rewards(OWNER, INITIAL_REWARD_RESERVE);
+    state.claim_unpaid_rewards_expect_error(USER, ERROR_NO_UNPAID_REWARDS);
+    state.claim_unpaid_rewards(OTHER_USER);
+    state.check_reward_balance(OTHER_USER, expected_rewards(STAKE_AMOUNT, 10));
+}
+
+#[test]
+fn withdraw_cannot_exceed_reserve() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_rewards(
+            FARM_ID,
+            BigUint::from(INITIAL_REWARD_RESERVE + 1),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_INSUFFICIENT_RESERVE)))
+        .run();
+
+    state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE);
+    assert_eq!(state.get_farm_info().rewards.get(0).rewards_reserve, 0u64);
+}
@@ -0,0 +1,183 @@
+mod setup;
+
+use errors::{ERROR_NO_REWARDS_APPLICABLE, ERROR_WRONG_TIMEFRAME};
+use setup::*;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+#[test]
+fn stake_before_start_earns_only_from_start() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        0u64.into()
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS - 1_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        0u64.into()
+    );
+    state.claim_rewards_expect_error(USER, 1, STAKE_AMOUNT, ERROR_NO_REWARDS_APPLICABLE);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        expected_rewards(STAKE_AMOUNT, 10).into()
+    );
+
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 10));
+    state.check_share_token(USER, 1, STAKE_AMOUNT, START_TS_MS + 10_000);
+}
+
+#[test]
+fn no_rewards_accrue_after_end() {
+    let mut state = TokenStakingTestState::new();
+
+    state.set_block_timestamp_ms(END_TS_MS - 20_000);
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(END_TS_MS + 500_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        expected_rewards(STAKE_AMOUNT, 20).into()
+    );
+
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 20));
+
+    state.set_block_timestamp_ms(END_TS_MS + 900_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        0u64.into()
+    );
+    state.claim_rewards_expect_error(USER, 1, STAKE_AMOUNT, ERROR_NO_REWARDS_APPLICABLE);
+}
+
+#[test]
+fn unstake_after_end_pays_rewards_up_to_end() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(END_TS_MS + 100_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    let window_seconds = (END_TS_MS - START_TS_MS) / 1_000;
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, window_seconds));
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+}
+
+#[test]
+fn accrual_is_bounded_exactly_by_window_edges() {
+    let mut state = TokenStakingTestState::new();
+
+    state.set_block_timestamp_ms(START_TS_MS);
+    state.stake(USER, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        0u64.into()
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 1_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        expected_rewards(STAKE_AMOUNT, 1).into()
+    );
+
+    let window_seconds = (END_TS_MS - START_TS_MS) / 1_000;
+    state.set_block_timestamp_ms(END_TS_MS);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, window_seconds));
+
+    state.set_block_timestamp_ms(END_TS_MS + 1_000);
+    state.claim_rewards_expect_error(USER, 1, STAKE_AMOUNT, ERROR_NO_REWARDS_APPLICABLE);
+}
+
+#[test]
+fn stake_after_end_earns_nothing() {
+    let mut state = TokenStakingTestState::new();
+
+    state.set_block_timestamp_ms(END_TS_MS + 1_000);
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(END_TS_MS + 100_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        0u64.into()
+    );
+
+    state.claim_rewards_expect_error(USER, 1, STAKE_AMOUNT, ERROR_NO_REWARDS_APPLICABLE);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_reward_balance(USER, 0);
+    assert_eq!(state.get_total_staked(), 0u64.into());
+}
+
+#[test]
+fn merged_stake_counts_only_window_time() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 5_000);
+    state.stake_and_merge(USER, STAKE_AMOUNT, 1, STAKE_AMOUNT);
+
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 5));
+    state.check_share_token(USER, 1, STAKE_AMOUNT * 2, START_TS_MS + 5_000);
+}
+
+#[test]
+fn postponed_start_delays_accrual() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    let new_start_ts_ms = START_TS_MS + 30_000;
+    state.modify_start_ts(new_start_ts_ms);
+
+    state.set_block_timestamp_ms(START_TS_MS + 40_000);
+    assert_eq!(
+        state.get_claimable_rewards(USER, 1, STAKE_AMOUNT),
+        expected_rewards(STAKE_AMOUNT, 10).into()
+    );
+}
+
+#[test]
+fn extended_and_shortened_end_bound_accrual() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(END_TS_MS - 10_000);
+    state.modify_end_ts(END_TS_MS + 50_000);
+
+    state.set_block_timestamp_ms(END_TS_MS + 100_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+    let extended_seconds = (END_TS_MS + 50_000 - START_TS_MS) / 1_000;
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, extended_seconds));
+
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.modify_end_ts(START_TS_MS + 20_000);
+
+    state.set_block_timestamp_ms(END_TS_MS);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 20));
+}
+
+#[test]
+fn window_cannot_be_inverted() {
+    let mut state = TokenStakingTestState::new();
+
+    state.modify_start_ts_expect_error(END_TS_MS, ERROR_WRONG_TIMEFRAME);
+    state.modify_end_ts_expect_error(START_TS_MS, ERROR_WRONG_TIMEFRAME);
+}
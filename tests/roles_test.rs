@@ -0,0 +1,210 @@
+mod setup;
+
+use errors::ERROR_MISSING_ROLE;
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::Role;
+
+fn has_role(state: &mut TokenStakingTestState, role: Role, address: TestAddress) -> bool {
+    state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .has_role(role, address.to_address())
+        .returns(ReturnsResult)
+        .run()
+}
+
+#[test]
+fn pauser_can_only_pause() {
+    let mut state = TokenStakingTestState::new();
+
+    state.grant_role(Role::Pauser, ADMIN);
+    assert!(has_role(&mut state, Role::Pauser, ADMIN));
+    assert!(!has_role(&mut state, Role::Treasurer, ADMIN));
+
+    state.pause(ADMIN);
+    state.unpause(ADMIN);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .modify_rewards(
+            FARM_ID,
+            BigUint::from(REWARD_PER_SEC * 2),
+            BigUint::from(REWARD_PER_SHARE),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_lock_tier(FARM_ID, 10_000u64, 12_000u64)
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+
+    state.revoke_role(Role::Pauser, ADMIN);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .pause()
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+}
+
+#[test]
+fn farm_endpoints_check_their_role() {
+    let mut state = TokenStakingTestState::new();
+
+    state.grant_role(Role::Treasurer, ADMIN);
+    state.grant_role(Role::RateManager, OTHER_USER);
+    state.grant_role(Role::ScheduleManager, USER);
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_rewards(
+            FARM_ID,
+            BigUint::from(1_000u64),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .run();
+    state
+        .world
+        .check_account(OWNER)
+        .esdt_balance(REWARD_TOKEN, INITIAL_REWARD_RESERVE * 9 + 1_000);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .modify_end_ts(
+            FARM_ID,
+            END_TS_MS + 10_000,
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OTHER_USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .modify_rewards(
+            FARM_ID,
+            BigUint::from(REWARD_PER_SEC * 2),
+            BigUint::from(REWARD_PER_SHARE),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+        )
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .modify_end_ts(
+            FARM_ID,
+            END_TS_MS + 10_000,
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+        )
+        .run();
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).end_ts_ms,
+        END_TS_MS + 10_000
+    );
+}
+
+#[test]
+fn admins_only_hold_the_roles_granted_to_them() {
+    let mut state = TokenStakingTestState::new();
+
+    state.add_admin(ADMIN);
+    assert!(state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .is_admin(ADMIN.to_address())
+        .returns(ReturnsResult)
+        .run());
+    assert!(!has_role(&mut state, Role::Treasurer, ADMIN));
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_unbonding_period(FARM_ID, 1_000u64)
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .pause()
+        .with_result(ExpectError(4, as_str(ERROR_MISSING_ROLE)))
+        .run();
+
+    state.grant_role(Role::ScheduleManager, ADMIN);
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_unbonding_period(FARM_ID, 1_000u64)
+        .run();
+}
+
+#[test]
+fn only_owner_manages_roles() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .grant_role(Role::Pauser, MultiValueVec::from(vec![ADMIN.to_address()]))
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    state.grant_role(Role::Pauser, ADMIN);
+    state.grant_role(Role::Pauser, USER);
+    state.revoke_role(Role::Pauser, USER);
+
+    let members = state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .role_members(Role::Pauser)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    assert_eq!(
+        members.into_vec(),
+        vec![OWNER.to_address(), ADMIN.to_address()]
+    );
+}
@@ -0,0 +1,1432 @@
+#![allow(dead_code)]
+
+#[path = "../../output/tokenstaking_proxy.rs"]
+pub mod tokenstaking_proxy;
+
+use multiversx_sc_scenario::{imports::*, scenario_model::TransferStep};
+use structs::{
+    EarlyUnstakePenalty, EmissionMode, Farm, PenaltyDestination, PenaltySource, PendingAction,
+    RateCheckpoint, Role, ShareTokenAttributes, Unbonding,
+};
+use tokenstaking::farm::FarmModule;
+use tokenstaking_proxy::TemplateProxy;
+
+pub const CODE_PATH: MxscPath = MxscPath::new("output/tokenstaking.mxsc.json");
+
+pub const OWNER: TestAddress = TestAddress::new("owner");
+pub const ADMIN: TestAddress = TestAddress::new("admin");
+pub const USER: TestAddress = TestAddress::new("user");
+pub const OTHER_USER: TestAddress = TestAddress::new("other-user");
+pub const SC: TestSCAddress = TestSCAddress::new("tokenstaking");
+
+pub const STAKED_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("STK-123456");
+pub const REWARD_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("RWD-123456");
+pub const SECOND_REWARD_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("RWDB-123456");
+pub const SHARE_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("SHARE-123456");
+pub const SECOND_STAKED_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("STKB-123456");
+pub const SECOND_SHARE_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("SHAREB-123456");
+
+pub const FARM_ID: u64 = 1;
+
+pub const TOKEN_ISSUANCE_COST: u64 = 50_000_000_000_000_000;
+
+pub const DEPLOY_TS_MS: u64 = 1_000;
+pub const START_TS_MS: u64 = 100_000;
+pub const END_TS_MS: u64 = 200_000;
+
+pub const REWARD_PER_SEC: u64 = 2;
+pub const REWARD_PER_SHARE: u64 = 10;
+
+pub const INITIAL_STAKED_BALANCE: u64 = 1_000_000;
+pub const INITIAL_REWARD_RESERVE: u64 = 1_000_000_000;
+
+pub struct TokenStakingTestState {
+    pub world: ScenarioWorld,
+}
+
+impl TokenStakingTestState {
+    /// Deploys the contract without creating the farm.
+    pub fn deploy() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.set_current_dir_from_workspace("");
+        world.register_contract(CODE_PATH, tokenstaking::ContractBuilder);
+
+        world
+            .account(OWNER)
+            .nonce(1)
+            .balance(TOKEN_ISSUANCE_COST * 10)
+            .esdt_balance(REWARD_TOKEN, INITIAL_REWARD_RESERVE * 10)
+            .esdt_balance(SECOND_REWARD_TOKEN, INITIAL_REWARD_RESERVE * 10)
+            .esdt_balance(STAKED_TOKEN, INITIAL_REWARD_RESERVE);
+        world.account(ADMIN).nonce(1);
+        world
+            .account(USER)
+            .nonce(1)
+            .esdt_balance(STAKED_TOKEN, INITIAL_STAKED_BALANCE)
+            .esdt_balance(SECOND_STAKED_TOKEN, INITIAL_STAKED_BALANCE);
+        world
+            .account(OTHER_USER)
+            .nonce(1)
+            .esdt_balance(STAKED_TOKEN, INITIAL_STAKED_BALANCE)
+            .esdt_balance(SECOND_STAKED_TOKEN, INITIAL_STAKED_BALANCE);
+
+        world.current_block().block_timestamp_ms(DEPLOY_TS_MS);
+
+        world
+            .tx()
+            .from(OWNER)
+            .typed(TemplateProxy)
+            .init()
+            .code(CODE_PATH)
+            .new_address(SC)
+            .run();
+
+        Self { world }
+    }
+
+    /// Deploys the contract, creates a `FixedRate` farm, registers `OWNER` as admin holding every
+    /// role and funds the reserve.
+    pub fn new() -> Self {
+        Self::new_with_mode(EmissionMode::FixedRate)
+    }
+
+    pub fn new_with_mode(emission_mode: EmissionMode) -> Self {
+        let mut state = Self::deploy();
+
+        state.create_farm(
+            START_TS_MS,
+            END_TS_MS,
+            REWARD_PER_SEC,
+            REWARD_PER_SHARE,
+            emission_mode,
+        );
+        state.add_admin(OWNER);
+        state.grant_all_roles(OWNER);
+        state.deposit_rewards(OWNER, INITIAL_REWARD_RESERVE);
+
+        state
+    }
+
+    /// Same as `new`, but the first farm is seeded instead of created, so that the next
+    /// farm can still go through `createFarm`.
+    ///
+    /// The scenario VM only runs the first issuance of a world: it then registers the system
+    /// SC as an account and panics on any later `registerAndSetAllRolesDynamic` call.
+    pub fn new_for_second_farm() -> Self {
+        let mut state = Self::deploy();
+
+        state.seed_farm();
+        state.add_admin(OWNER);
+        state.grant_all_roles(OWNER);
+        state.deposit_rewards(OWNER, INITIAL_REWARD_RESERVE);
+
+        state
+    }
+
+    // === Setup ===
+
+    /// Calls `createFarm` and then completes the share token issuance.
+    ///
+    /// The scenario VM cannot issue dynamic tokens, so the issuance callback is invoked
+    /// directly and the local roles are granted to the contract.
+    pub fn create_farm(
+        &mut self,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: u64,
+        reward_per_share: u64,
+        emission_mode: EmissionMode,
+    ) {
+        self.create_farm_with_tokens(
+            STAKED_TOKEN,
+            REWARD_TOKEN,
+            SHARE_TOKEN,
+            start_ts_ms,
+            end_ts_ms,
+            reward_per_sec,
+            reward_per_share,
+            emission_mode,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_farm_with_tokens(
+        &mut self,
+        staked_token: TestTokenIdentifier,
+        reward_token: TestTokenIdentifier,
+        share_token: TestTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: u64,
+        reward_per_share: u64,
+        emission_mode: EmissionMode,
+    ) {
+        self.create_farm_endpoint(
+            staked_token,
+            reward_token,
+            start_ts_ms,
+            end_ts_ms,
+            reward_per_sec,
+            reward_per_share,
+            emission_mode,
+        );
+        self.complete_share_token_issuance(
+            staked_token,
+            reward_token,
+            share_token,
+            start_ts_ms,
+            end_ts_ms,
+            reward_per_sec,
+            reward_per_share,
+            emission_mode,
+        );
+    }
+
+    /// Allocates the id of a `FixedRate` farm the way `createFarm` does and completes its
+    /// issuance, without sending the issuance call.
+    fn seed_farm(&mut self) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .whitebox(tokenstaking::contract_obj, |sc| {
+                sc.last_farm_id().update(|id| *id += 1);
+            });
+
+        self.complete_share_token_issuance(
+            STAKED_TOKEN,
+            REWARD_TOKEN,
+            SHARE_TOKEN,
+            START_TS_MS,
+            END_TS_MS,
+            REWARD_PER_SEC,
+            REWARD_PER_SHARE,
+            EmissionMode::FixedRate,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn complete_share_token_issuance(
+        &mut self,
+        staked_token: TestTokenIdentifier,
+        reward_token: TestTokenIdentifier,
+        share_token: TestTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: u64,
+        reward_per_share: u64,
+        emission_mode: EmissionMode,
+    ) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .whitebox(tokenstaking::contract_obj, |sc| {
+                sc.share_token_issuance_callback(
+                    sc.last_farm_id().get(),
+                    &OWNER.to_managed_address(),
+                    &EgldOrEsdtTokenIdentifier::esdt(staked_token.to_token_identifier()),
+                    &EgldOrEsdtTokenIdentifier::esdt(reward_token.to_token_identifier()),
+                    start_ts_ms,
+                    end_ts_ms,
+                    &BigUint::from(reward_per_sec),
+                    &BigUint::from(reward_per_share),
+                    emission_mode,
+                    ManagedAsyncCallResult::Ok(share_token.to_token_identifier()),
+                );
+            });
+
+        self.world.set_esdt_local_roles(
+            SC,
+            share_token.as_bytes(),
+            &[
+                EsdtLocalRole::NftCreate,
+                EsdtLocalRole::NftBurn,
+                EsdtLocalRole::NftAddQuantity,
+                EsdtLocalRole::NftUpdateAttributes,
+            ],
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_farm_endpoint(
+        &mut self,
+        staked_token: TestTokenIdentifier,
+        reward_token: TestTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: u64,
+        reward_per_share: u64,
+        emission_mode: EmissionMode,
+    ) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .create_farm(
+                staked_token.to_token_identifier(),
+                reward_token.to_token_identifier(),
+                start_ts_ms,
+                end_ts_ms,
+                BigUint::from(reward_per_sec),
+                BigUint::from(reward_per_share),
+                ManagedBuffer::from("StakingShare"),
+                ManagedBuffer::from("SHARE"),
+                OptionalValue::Some(emission_mode),
+            )
+            .egld(TOKEN_ISSUANCE_COST)
+            .run();
+    }
+
+    pub fn set_block_timestamp_ms(&mut self, timestamp_ms: u64) {
+        self.world.current_block().block_timestamp_ms(timestamp_ms);
+    }
+
+    // === Admin ===
+
+    pub fn add_admin(&mut self, address: TestAddress) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .add_admin(MultiValueVec::from(vec![address.to_address()]))
+            .run();
+    }
+
+    pub fn grant_role(&mut self, role: Role, address: TestAddress) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .grant_role(role, MultiValueVec::from(vec![address.to_address()]))
+            .run();
+    }
+
+    pub fn grant_all_roles(&mut self, address: TestAddress) {
+        for role in [
+            Role::Pauser,
+            Role::RateManager,
+            Role::Treasurer,
+            Role::ScheduleManager,
+        ] {
+            self.grant_role(role, address);
+        }
+    }
+
+    pub fn revoke_role(&mut self, role: Role, address: TestAddress) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .revoke_role(role, MultiValueVec::from(vec![address.to_address()]))
+            .run();
+    }
+
+    pub fn pause(&mut self, from: TestAddress) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .pause()
+            .run();
+    }
+
+    pub fn unpause(&mut self, from: TestAddress) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .unpause()
+            .run();
+    }
+
+    pub fn set_emergency_mode(&mut self, from: TestAddress, enabled: bool) {
+        let tx = self.world.tx().from(from).to(SC).typed(TemplateProxy);
+        if enabled {
+            tx.enable_emergency_mode().run();
+        } else {
+            tx.disable_emergency_mode().run();
+        }
+    }
+
+    pub fn add_reward_token(
+        &mut self,
+        reward_token: TestTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        reward_per_sec: u64,
+        reward_per_share: u64,
+    ) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .add_reward_token(
+                FARM_ID,
+                EgldOrEsdtTokenIdentifier::esdt(reward_token.to_token_identifier()),
+                start_ts_ms,
+                end_ts_ms,
+                BigUint::from(reward_per_sec),
+                BigUint::from(reward_per_share),
+            )
+            .run();
+    }
+
+    pub fn add_reward_token_expect_error(
+        &mut self,
+        reward_token: TestTokenIdentifier,
+        start_ts_ms: u64,
+        end_ts_ms: u64,
+        error: &[u8],
+    ) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .add_reward_token(
+                FARM_ID,
+                EgldOrEsdtTokenIdentifier::esdt(reward_token.to_token_identifier()),
+                start_ts_ms,
+                end_ts_ms,
+                BigUint::from(REWARD_PER_SEC),
+                BigUint::from(REWARD_PER_SHARE),
+            )
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn deposit_rewards(&mut self, from: TestAddress, amount: u64) {
+        self.deposit_reward_token(from, REWARD_TOKEN, amount);
+    }
+
+    pub fn deposit_reward_token(
+        &mut self,
+        from: TestAddress,
+        reward_token: TestTokenIdentifier,
+        amount: u64,
+    ) {
+        self.deposit_rewards_in_farm(from, FARM_ID, reward_token, amount);
+    }
+
+    pub fn deposit_rewards_in_farm(
+        &mut self,
+        from: TestAddress,
+        farm_id: u64,
+        reward_token: TestTokenIdentifier,
+        amount: u64,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .deposit_rewards(farm_id)
+            .single_esdt(
+                &reward_token.to_token_identifier(),
+                0,
+                &BigUint::from(amount),
+            )
+            .run();
+    }
+
+    pub fn modify_rewards(&mut self, new_reward_per_sec: u64, new_reward_per_share: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .modify_rewards(
+                FARM_ID,
+                BigUint::from(new_reward_per_sec),
+                BigUint::from(new_reward_per_share),
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .run();
+    }
+
+    pub fn modify_reward_token_rewards(
+        &mut self,
+        reward_token: TestTokenIdentifier,
+        new_reward_per_sec: u64,
+        new_reward_per_share: u64,
+    ) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .modify_rewards(
+                FARM_ID,
+                BigUint::from(new_reward_per_sec),
+                BigUint::from(new_reward_per_share),
+                OptionalValue::Some(EgldOrEsdtTokenIdentifier::esdt(
+                    reward_token.to_token_identifier(),
+                )),
+            )
+            .run();
+    }
+
+    pub fn modify_start_ts(&mut self, new_start_ts_ms: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .modify_start_ts(
+                FARM_ID,
+                new_start_ts_ms,
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .run();
+    }
+
+    pub fn modify_end_ts(&mut self, new_end_ts_ms: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .modify_end_ts(
+                FARM_ID,
+                new_end_ts_ms,
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .run();
+    }
+
+    pub fn set_timelock_delay(&mut self, delay_ms: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_timelock_delay(delay_ms)
+            .run();
+    }
+
+    pub fn set_approval_threshold(&mut self, threshold: usize) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_approval_threshold(threshold)
+            .run();
+    }
+
+    pub fn approve_action(&mut self, from: TestAddress, action_id: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .approve_action(action_id)
+            .run();
+    }
+
+    pub fn approve_action_expect_error(&mut self, from: TestAddress, action_id: u64, error: &[u8]) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .approve_action(action_id)
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn execute_action(&mut self, from: TestAddress, action_id: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .execute_action(action_id)
+            .run();
+    }
+
+    pub fn execute_action_expect_error(&mut self, from: TestAddress, action_id: u64, error: &[u8]) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .execute_action(action_id)
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn set_lock_tier(&mut self, lock_period_ms: u64, multiplier_bps: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_lock_tier(FARM_ID, lock_period_ms, multiplier_bps)
+            .run();
+    }
+
+    pub fn set_min_stake_duration(&mut self, min_stake_duration_ms: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_min_stake_duration(FARM_ID, min_stake_duration_ms)
+            .run();
+    }
+
+    pub fn set_unbonding_period(&mut self, unbonding_period_ms: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_unbonding_period(FARM_ID, unbonding_period_ms)
+            .run();
+    }
+
+    pub fn set_early_unstake_penalty(
+        &mut self,
+        penalty_bps: u64,
+        source: PenaltySource,
+        destination: PenaltyDestination<StaticApi>,
+    ) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_early_unstake_penalty(FARM_ID, penalty_bps, source, destination)
+            .run();
+    }
+
+    pub fn set_solvency_check(&mut self, enabled: bool) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .set_solvency_check(FARM_ID, enabled)
+            .run();
+    }
+
+    pub fn withdraw_reward_token(&mut self, reward_token: TestTokenIdentifier, amount: u64) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .withdraw_rewards(
+                FARM_ID,
+                BigUint::from(amount),
+                OptionalValue::Some(EgldOrEsdtTokenIdentifier::esdt(
+                    reward_token.to_token_identifier(),
+                )),
+                OptionalValue::<ManagedAddress<StaticApi>>::None,
+            )
+            .run();
+    }
+
+    pub fn modify_end_ts_expect_error(&mut self, new_end_ts_ms: u64, error: &[u8]) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .modify_end_ts(
+                FARM_ID,
+                new_end_ts_ms,
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn modify_start_ts_expect_error(&mut self, new_start_ts_ms: u64, error: &[u8]) {
+        self.world
+            .tx()
+            .from(OWNER)
+            .to(SC)
+            .typed(TemplateProxy)
+            .modify_start_ts(
+                FARM_ID,
+                new_start_ts_ms,
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    // === User ===
+
+    pub fn stake(&mut self, from: TestAddress, amount: u64) {
+        self.stake_in_farm(from, FARM_ID, STAKED_TOKEN, amount);
+    }
+
+    pub fn stake_in_farm(
+        &mut self,
+        from: TestAddress,
+        farm_id: u64,
+        staked_token: TestTokenIdentifier,
+        amount: u64,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .stake(farm_id, OptionalValue::<u64>::None)
+            .single_esdt(
+                &staked_token.to_token_identifier(),
+                0,
+                &BigUint::from(amount),
+            )
+            .run();
+    }
+
+    pub fn stake_in_farm_expect_error(
+        &mut self,
+        from: TestAddress,
+        farm_id: u64,
+        staked_token: TestTokenIdentifier,
+        amount: u64,
+        error: &[u8],
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .stake(farm_id, OptionalValue::<u64>::None)
+            .single_esdt(
+                &staked_token.to_token_identifier(),
+                0,
+                &BigUint::from(amount),
+            )
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn stake_locked(&mut self, from: TestAddress, amount: u64, lock_period_ms: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .stake(FARM_ID, OptionalValue::Some(lock_period_ms))
+            .single_esdt(
+                &STAKED_TOKEN.to_token_identifier(),
+                0,
+                &BigUint::from(amount),
+            )
+            .run();
+    }
+
+    /// Stakes `amount` and merges the given share token into the new position.
+    pub fn stake_and_merge(
+        &mut self,
+        from: TestAddress,
+        amount: u64,
+        share_nonce: u64,
+        share_amount: u64,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .stake(FARM_ID, OptionalValue::<u64>::None)
+            .esdt(TestEsdtTransfer(STAKED_TOKEN, 0, amount))
+            .esdt(TestEsdtTransfer(SHARE_TOKEN, share_nonce, share_amount))
+            .run();
+    }
+
+    pub fn claim_rewards(&mut self, from: TestAddress, share_nonce: u64, share_amount: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .claim_rewards_endpoint(OptionalValue::<bool>::None)
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn settle_expired_boost(&mut self, from: TestAddress, share_nonce: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .settle_expired_boost_endpoint(FARM_ID, share_nonce)
+            .run();
+    }
+
+    pub fn settle_expired_boost_expect_error(
+        &mut self,
+        from: TestAddress,
+        share_nonce: u64,
+        error: &[u8],
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .settle_expired_boost_endpoint(FARM_ID, share_nonce)
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn stake_for(&mut self, from: TestAddress, recipient: TestAddress, amount: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .stake_for(FARM_ID, recipient, OptionalValue::<u64>::None)
+            .single_esdt(
+                &STAKED_TOKEN.to_token_identifier(),
+                0,
+                &BigUint::from(amount),
+            )
+            .run();
+    }
+
+    pub fn unstake_to(
+        &mut self,
+        from: TestAddress,
+        recipient: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+        unstake_amount: u64,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .unstake_to(
+                recipient,
+                OptionalValue::Some(BigUint::from(unstake_amount)),
+            )
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn claim_rewards_to(
+        &mut self,
+        from: TestAddress,
+        recipient: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .claim_rewards_to(recipient)
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn claim_and_compound(&mut self, from: TestAddress, share_nonce: u64, share_amount: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .claim_rewards_endpoint(OptionalValue::Some(true))
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn compound(&mut self, from: TestAddress, share_nonce: u64, share_amount: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .compound()
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn claim_unpaid_rewards(&mut self, from: TestAddress) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .claim_unpaid_rewards(FARM_ID)
+            .run();
+    }
+
+    pub fn claim_unpaid_rewards_expect_error(&mut self, from: TestAddress, error: &[u8]) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .claim_unpaid_rewards(FARM_ID)
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn claim_rewards_expect_error(
+        &mut self,
+        from: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+        error: &[u8],
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .claim_rewards_endpoint(OptionalValue::<bool>::None)
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn unstake(&mut self, from: TestAddress, share_nonce: u64, share_amount: u64) {
+        self.unstake_share_token(from, SHARE_TOKEN, share_nonce, share_amount);
+    }
+
+    pub fn unstake_share_token(
+        &mut self,
+        from: TestAddress,
+        share_token: TestTokenIdentifier,
+        share_nonce: u64,
+        share_amount: u64,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .unstake(OptionalValue::<BigUint<StaticApi>>::None)
+            .single_esdt(
+                &share_token.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn unstake_expect_error(
+        &mut self,
+        from: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+        error: &[u8],
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .unstake(OptionalValue::<BigUint<StaticApi>>::None)
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn unstake_partial(
+        &mut self,
+        from: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+        unstake_amount: u64,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .unstake(OptionalValue::Some(BigUint::from(unstake_amount)))
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn emergency_unstake(&mut self, from: TestAddress, share_nonce: u64, share_amount: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .emergency_unstake()
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .run();
+    }
+
+    pub fn emergency_unstake_expect_error(
+        &mut self,
+        from: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+        error: &[u8],
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .emergency_unstake()
+            .single_esdt(
+                &SHARE_TOKEN.to_token_identifier(),
+                share_nonce,
+                &BigUint::from(share_amount),
+            )
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn withdraw(&mut self, from: TestAddress) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .withdraw(FARM_ID)
+            .run();
+    }
+
+    pub fn withdraw_expect_error(&mut self, from: TestAddress, error: &[u8]) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .withdraw(FARM_ID)
+            .with_result(ExpectError(4, as_str(error)))
+            .run();
+    }
+
+    pub fn cancel_unbonding(&mut self, from: TestAddress, unbonding_id: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(SC)
+            .typed(TemplateProxy)
+            .cancel_unbonding(FARM_ID, unbonding_id, OptionalValue::<u64>::None)
+            .run();
+    }
+
+    pub fn transfer_share_token(
+        &mut self,
+        from: TestAddress,
+        to: TestAddress,
+        nonce: u64,
+        amount: u64,
+    ) {
+        self.world
+            .transfer_step(TransferStep::new().from(from).to(to).esdt_transfer(
+                SHARE_TOKEN.as_bytes(),
+                nonce,
+                amount,
+            ));
+    }
+
+    // === Views ===
+
+    pub fn get_farm_info(&mut self) -> Farm<StaticApi> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_farm_info(FARM_ID)
+            .returns(ReturnsResult)
+            .run()
+    }
+
+    pub fn get_farms(&mut self, from: usize, size: usize) -> Vec<Farm<StaticApi>> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_farms(from, size)
+            .returns(ReturnsResult)
+            .run()
+            .to_vec()
+            .into_vec()
+    }
+
+    pub fn get_pending_actions(&mut self) -> Vec<PendingAction<StaticApi>> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_pending_actions()
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .into_vec()
+    }
+
+    pub fn get_timelock_delay(&mut self) -> u64 {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .timelock_delay_ms()
+            .returns(ReturnsResult)
+            .run()
+    }
+
+    pub fn get_action_approvers(&mut self, action_id: u64) -> Vec<Address> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .action_approvers(action_id)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .into_vec()
+    }
+
+    pub fn get_rate_history(&mut self) -> Vec<RateCheckpoint<StaticApi>> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_rate_history(
+                FARM_ID,
+                EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN.to_token_identifier()),
+            )
+            .returns(ReturnsResult)
+            .run()
+            .to_vec()
+            .into_vec()
+    }
+
+    pub fn get_total_staked(&mut self) -> RustBigUint {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .total_staked(FARM_ID)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+    }
+
+    pub fn get_user_staked(&mut self, address: TestAddress) -> RustBigUint {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .user_staked(FARM_ID, address.to_address())
+            .returns(ReturnsResultUnmanaged)
+            .run()
+    }
+
+    /// Claimable rewards in the farm's primary reward token.
+    pub fn get_claimable_rewards(
+        &mut self,
+        address: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+    ) -> RustBigUint {
+        self.get_claimable_reward_token(address, share_nonce, share_amount, REWARD_TOKEN)
+    }
+
+    pub fn get_claimable_reward_token(
+        &mut self,
+        address: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+        reward_token: TestTokenIdentifier,
+    ) -> RustBigUint {
+        let rewards = self
+            .world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_claimable_rewards(
+                FARM_ID,
+                address.to_address(),
+                MultiValueVec::from(vec![MultiValue2::from((
+                    share_nonce,
+                    BigUint::<StaticApi>::from(share_amount),
+                ))]),
+            )
+            .returns(ReturnsResult)
+            .run()
+            .to_vec();
+
+        let reward_token = EgldOrEsdtTokenIdentifier::esdt(reward_token.to_token_identifier());
+        rewards
+            .iter()
+            .find(|reward| reward.token_identifier == reward_token)
+            .map(|reward| RustBigUint::from_bytes_be(reward.amount.to_bytes_be().as_slice()))
+            .unwrap_or_default()
+    }
+
+    pub fn get_accrued_rewards(&mut self) -> RustBigUint {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_accrued_rewards_view(
+                FARM_ID,
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .returns(ReturnsResultUnmanaged)
+            .run()
+    }
+
+    pub fn get_boosted_share_supply(&mut self) -> RustBigUint {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .boosted_share_supply(FARM_ID)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+    }
+
+    pub fn get_projected_obligations(&mut self) -> RustBigUint {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_projected_obligations_view(
+                FARM_ID,
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .returns(ReturnsResultUnmanaged)
+            .run()
+    }
+
+    pub fn get_rewards_runway(&mut self) -> u64 {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_rewards_runway(
+                FARM_ID,
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            )
+            .returns(ReturnsResult)
+            .run()
+    }
+
+    /// Unpaid rewards of an address in the farm's primary reward token.
+    pub fn get_unpaid_rewards(&mut self, address: TestAddress) -> RustBigUint {
+        let rewards = self
+            .world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_unpaid_rewards(FARM_ID, address.to_address())
+            .returns(ReturnsResult)
+            .run()
+            .to_vec();
+
+        let reward_token = EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN.to_token_identifier());
+        rewards
+            .iter()
+            .find(|reward| reward.token_identifier == reward_token)
+            .map(|reward| RustBigUint::from_bytes_be(reward.amount.to_bytes_be().as_slice()))
+            .unwrap_or_default()
+    }
+
+    pub fn get_unbondings(&mut self, address: TestAddress) -> Vec<Unbonding<StaticApi>> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_unbondings(FARM_ID, address.to_address())
+            .returns(ReturnsResult)
+            .run()
+            .to_vec()
+            .into_vec()
+    }
+
+    pub fn get_early_unstake_penalty_config(&mut self) -> Option<EarlyUnstakePenalty<StaticApi>> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_early_unstake_penalty_config(FARM_ID)
+            .returns(ReturnsResult)
+            .run()
+            .into_option()
+    }
+
+    /// Early unstake penalty of a share token, as `(token, amount)` pairs.
+    pub fn get_early_unstake_penalty(
+        &mut self,
+        address: TestAddress,
+        share_nonce: u64,
+        share_amount: u64,
+    ) -> Vec<(EgldOrEsdtTokenIdentifier<StaticApi>, RustBigUint)> {
+        self.world
+            .query()
+            .to(SC)
+            .typed(TemplateProxy)
+            .get_early_unstake_penalty(
+                FARM_ID,
+                address.to_address(),
+                share_nonce,
+                BigUint::<StaticApi>::from(share_amount),
+            )
+            .returns(ReturnsResult)
+            .run()
+            .to_vec()
+            .iter()
+            .map(|penalty| {
+                (
+                    penalty.token_identifier.clone(),
+                    RustBigUint::from_bytes_be(penalty.amount.to_bytes_be().as_slice()),
+                )
+            })
+            .collect()
+    }
+
+    // === Checks ===
+
+    pub fn check_reward_balance(&mut self, address: TestAddress, amount: u64) {
+        self.check_reward_token_balance(address, REWARD_TOKEN, amount);
+    }
+
+    pub fn check_reward_token_balance(
+        &mut self,
+        address: TestAddress,
+        reward_token: TestTokenIdentifier,
+        amount: u64,
+    ) {
+        self.world
+            .check_account(address)
+            .esdt_balance(reward_token, amount);
+    }
+
+    pub fn check_staked_balance(&mut self, address: TestAddress, amount: u64) {
+        self.world
+            .check_account(address)
+            .esdt_balance(STAKED_TOKEN, amount);
+    }
+
+    pub fn check_share_token(
+        &mut self,
+        address: TestAddress,
+        nonce: u64,
+        amount: u64,
+        update_ts_ms: u64,
+    ) {
+        let mut actual = (0u64, 0u64);
+        self.world
+            .query()
+            .to(SC)
+            .whitebox(tokenstaking::contract_obj, |sc| {
+                let token_data = sc.blockchain().get_esdt_token_data(
+                    &address.to_managed_address(),
+                    &SHARE_TOKEN.to_token_identifier(),
+                    nonce,
+                );
+                let attributes: ShareTokenAttributes<DebugApi> = token_data.decode_attributes();
+                actual = (token_data.amount.to_u64().unwrap(), attributes.update_ts_ms);
+            });
+
+        assert_eq!(actual, (amount, update_ts_ms));
+    }
+
+    /// Share token balance of any account, zero once the nonce has been burned.
+    pub fn get_share_token_balance(&mut self, address: TestAddress, nonce: u64) -> u64 {
+        let mut balance = 0u64;
+        self.world
+            .query()
+            .to(SC)
+            .whitebox(tokenstaking::contract_obj, |sc| {
+                balance = sc
+                    .blockchain()
+                    .get_esdt_token_data(
+                        &address.to_managed_address(),
+                        &SHARE_TOKEN.to_token_identifier(),
+                        nonce,
+                    )
+                    .amount
+                    .to_u64()
+                    .unwrap();
+            });
+
+        balance
+    }
+
+    /// Returns the `(lock_until_ts_ms, multiplier_bps)` of a share token.
+    pub fn get_share_token_lock(&mut self, address: TestAddress, nonce: u64) -> (u64, u64) {
+        let mut lock = (0u64, 0u64);
+        self.world
+            .query()
+            .to(SC)
+            .whitebox(tokenstaking::contract_obj, |sc| {
+                let attributes: ShareTokenAttributes<DebugApi> = sc
+                    .blockchain()
+                    .get_esdt_token_data(
+                        &address.to_managed_address(),
+                        &SHARE_TOKEN.to_token_identifier(),
+                        nonce,
+                    )
+                    .decode_attributes();
+                lock = (attributes.lock_until_ts_ms, attributes.multiplier_bps);
+            });
+
+        lock
+    }
+}
+
+pub fn as_str(error: &[u8]) -> &str {
+    core::str::from_utf8(error).unwrap()
+}
+
+/// Rewards earned by `amount` staked for `seconds` at the default farm rates.
+pub fn expected_rewards(amount: u64, seconds: u64) -> u64 {
+    REWARD_PER_SEC * seconds * amount / REWARD_PER_SHARE
+}
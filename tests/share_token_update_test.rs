@@ -0,0 +1,99 @@
+mod setup;
+
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use tokenstaking::rewards::RewardsModule;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+#[test]
+fn claim_updates_share_token_in_place() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_share_token(USER, 1, STAKE_AMOUNT, START_TS_MS + 10_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + 30_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_share_token(USER, 1, STAKE_AMOUNT, START_TS_MS + 30_000);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 30));
+
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+    state.check_share_token(OTHER_USER, 2, STAKE_AMOUNT, START_TS_MS + 30_000);
+}
+
+#[test]
+fn merged_share_tokens_keep_first_nonce() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .claim_rewards_endpoint(OptionalValue::<bool>::None)
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 1, STAKE_AMOUNT))
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 2, STAKE_AMOUNT))
+        .run();
+
+    state.check_share_token(USER, 1, STAKE_AMOUNT * 2, START_TS_MS + 10_000);
+    state
+        .world
+        .query()
+        .to(SC)
+        .whitebox(tokenstaking::contract_obj, |sc| {
+            let token_data = sc.blockchain().get_esdt_token_data(
+                &USER.to_managed_address(),
+                &SHARE_TOKEN.to_token_identifier(),
+                2,
+            );
+            assert_eq!(token_data.amount, 0u64);
+            assert!(sc.share_token_nonce_supply(FARM_ID, 2).is_empty());
+        });
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT * 2, 10));
+}
+
+#[test]
+fn partially_held_nonce_is_reminted() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.transfer_share_token(USER, OTHER_USER, 1, 400);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.claim_rewards(USER, 1, 600);
+    state.check_share_token(USER, 2, 600, START_TS_MS + 10_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.claim_rewards(OTHER_USER, 1, 400);
+    state.check_reward_balance(OTHER_USER, expected_rewards(400, 20));
+}
+
+#[test]
+fn untracked_nonce_falls_back_to_burn_and_remint() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .whitebox(tokenstaking::contract_obj, |sc| {
+            sc.share_token_nonce_supply(FARM_ID, 1).clear();
+        });
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+
+    state.check_share_token(USER, 2, STAKE_AMOUNT, START_TS_MS + 10_000);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 10));
+}
@@ -0,0 +1,134 @@
+mod setup;
+
+use errors::ERROR_RESERVE_BELOW_OBLIGATIONS;
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::EmissionMode;
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+/// Rewards emitted per second to `STAKE_AMOUNT` at the default rates.
+const EMISSION_PER_SEC: u64 = REWARD_PER_SEC * STAKE_AMOUNT / REWARD_PER_SHARE;
+
+const WINDOW_SECONDS: u64 = (END_TS_MS - START_TS_MS) / 1_000;
+
+#[test]
+fn accrued_rewards_follow_emission_and_payouts() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    assert_eq!(state.get_accrued_rewards(), 0u64.into());
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    assert_eq!(state.get_accrued_rewards(), (EMISSION_PER_SEC * 10).into());
+
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    assert_eq!(state.get_accrued_rewards(), (EMISSION_PER_SEC * 30).into());
+
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    assert_eq!(state.get_accrued_rewards(), (EMISSION_PER_SEC * 10).into());
+
+    state.unstake(OTHER_USER, 2, STAKE_AMOUNT);
+    assert_eq!(state.get_accrued_rewards(), 0u64.into());
+}
+
+#[test]
+fn runway_counts_seconds_covered_by_reserve() {
+    let mut state = TokenStakingTestState::new();
+
+    assert_eq!(state.get_rewards_runway(), u64::MAX);
+
+    state.stake(USER, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_rewards_runway(),
+        INITIAL_REWARD_RESERVE / EMISSION_PER_SEC
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    assert_eq!(
+        state.get_rewards_runway(),
+        (INITIAL_REWARD_RESERVE - EMISSION_PER_SEC * 10) / EMISSION_PER_SEC
+    );
+
+    state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE - EMISSION_PER_SEC * 15);
+    assert_eq!(state.get_rewards_runway(), 5);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    assert_eq!(state.get_rewards_runway(), 0);
+
+    state.set_block_timestamp_ms(END_TS_MS);
+    assert_eq!(state.get_rewards_runway(), u64::MAX);
+}
+
+#[test]
+fn projected_obligations_cover_remaining_window() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_projected_obligations(),
+        (EMISSION_PER_SEC * WINDOW_SECONDS).into()
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + 30_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_projected_obligations(),
+        (EMISSION_PER_SEC * (WINDOW_SECONDS - 30)).into()
+    );
+
+    let mut state = TokenStakingTestState::new_with_mode(EmissionMode::Pool);
+    state.stake(USER, STAKE_AMOUNT);
+    assert_eq!(
+        state.get_projected_obligations(),
+        (REWARD_PER_SEC * WINDOW_SECONDS).into()
+    );
+}
+
+#[test]
+fn solvency_check_refuses_underfunding_changes() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.set_solvency_check(true);
+
+    let obligations = EMISSION_PER_SEC * WINDOW_SECONDS;
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_rewards(
+            FARM_ID,
+            BigUint::from(INITIAL_REWARD_RESERVE - obligations + 1),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_RESERVE_BELOW_OBLIGATIONS)))
+        .run();
+    state.withdraw_reward_token(REWARD_TOKEN, INITIAL_REWARD_RESERVE - obligations);
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .modify_rewards(
+            FARM_ID,
+            BigUint::from(REWARD_PER_SEC * 2),
+            BigUint::from(REWARD_PER_SHARE),
+            OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, as_str(ERROR_RESERVE_BELOW_OBLIGATIONS)))
+        .run();
+    state.modify_end_ts_expect_error(END_TS_MS + 1_000, ERROR_RESERVE_BELOW_OBLIGATIONS);
+
+    state.modify_end_ts(END_TS_MS - 10_000);
+    state.modify_rewards(REWARD_PER_SEC, REWARD_PER_SHARE * 2);
+
+    state.set_solvency_check(false);
+    state.modify_end_ts(END_TS_MS + 100_000);
+}
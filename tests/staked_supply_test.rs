@@ -0,0 +1,50 @@
+mod setup;
+
+use setup::*;
+
+#[test]
+fn totals_follow_stake_merge_and_unstake() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, 1_000);
+    state.stake(OTHER_USER, 3_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.stake_and_merge(USER, 500, 1, 1_000);
+
+    assert_eq!(state.get_total_staked(), 4_500u64.into());
+    assert_eq!(state.get_user_staked(USER), 1_500u64.into());
+    assert_eq!(state.get_user_staked(OTHER_USER), 3_000u64.into());
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake_partial(USER, 1, 1_500, 600);
+
+    assert_eq!(state.get_total_staked(), 3_900u64.into());
+    assert_eq!(state.get_user_staked(USER), 900u64.into());
+
+    let farm = state.get_farm_info();
+    assert_eq!(farm.total_staked, 3_900u64);
+    assert_eq!(farm.total_share_supply, 3_900u64);
+
+    state
+        .world
+        .check_account(SC)
+        .esdt_balance(STAKED_TOKEN, 3_900u64);
+}
+
+#[test]
+fn user_total_is_floored_when_unstaking_transferred_shares() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, 1_000);
+    state.stake(OTHER_USER, 200);
+
+    state.transfer_share_token(USER, OTHER_USER, 1, 1_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.unstake(OTHER_USER, 1, 1_000);
+
+    assert_eq!(state.get_total_staked(), 200u64.into());
+    assert_eq!(state.get_user_staked(USER), 1_000u64.into());
+    assert_eq!(state.get_user_staked(OTHER_USER), 0u64.into());
+}
@@ -0,0 +1,222 @@
+mod setup;
+
+use errors::{
+    ERROR_INVALID_SHARE_TOKEN, ERROR_NO_REWARDS_APPLICABLE,
+    ERROR_ONLY_ONE_STAKING_TRANSFER_ALLOWED, ERROR_STAKED_TOKEN_MISSING,
+    ERROR_UNSTAKE_AMOUNT_EXCEEDS,
+};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+#[test]
+fn stake_mints_share_token_and_unstake_returns_principal() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.check_share_token(USER, 1, STAKE_AMOUNT, DEPLOY_TS_MS);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    assert_eq!(state.get_share_token_balance(USER, 1), 0);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 10));
+    assert_eq!(state.get_total_staked(), 0u64.into());
+}
+
+#[test]
+fn unstake_in_same_block_as_stake_returns_principal() {
+    let mut state = TokenStakingTestState::new();
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.stake(USER, STAKE_AMOUNT);
+    state.claim_rewards_expect_error(USER, 1, STAKE_AMOUNT, ERROR_NO_REWARDS_APPLICABLE);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    state.check_reward_balance(USER, 0);
+    assert_eq!(state.get_total_staked(), 0u64.into());
+    assert_eq!(state.get_unpaid_rewards(USER), 0u64.into());
+}
+
+#[test]
+fn merge_without_rewards_keeps_position() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake_and_merge(USER, STAKE_AMOUNT, 1, STAKE_AMOUNT);
+
+    state.check_share_token(USER, 1, STAKE_AMOUNT * 2, DEPLOY_TS_MS);
+    state.check_reward_balance(USER, 0);
+    assert_eq!(state.get_user_staked(USER), (STAKE_AMOUNT * 2).into());
+}
+
+#[test]
+fn stake_merges_several_share_tokens() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake(USER, STAKE_AMOUNT * 2);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .stake(FARM_ID, OptionalValue::<u64>::None)
+        .esdt(TestEsdtTransfer(STAKED_TOKEN, 0, 500))
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 1, STAKE_AMOUNT))
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 2, STAKE_AMOUNT * 2))
+        .run();
+
+    state.check_share_token(USER, 1, STAKE_AMOUNT * 3 + 500, START_TS_MS + 10_000);
+    assert_eq!(state.get_share_token_balance(USER, 2), 0);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT * 3, 10));
+    assert_eq!(state.get_total_staked(), (STAKE_AMOUNT * 3 + 500).into());
+}
+
+#[test]
+fn unstake_several_share_tokens_at_once() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake(USER, STAKE_AMOUNT);
+    state.stake(OTHER_USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .unstake(OptionalValue::Some(BigUint::from(STAKE_AMOUNT + 400)))
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 1, STAKE_AMOUNT))
+        .esdt(TestEsdtTransfer(SHARE_TOKEN, 2, STAKE_AMOUNT))
+        .run();
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - 600);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT * 2, 20));
+    state.check_share_token(USER, 1, 600, START_TS_MS + 20_000);
+    assert_eq!(state.get_share_token_balance(USER, 2), 0);
+    assert_eq!(state.get_user_staked(USER), 600u64.into());
+    assert_eq!(state.get_total_staked(), (STAKE_AMOUNT + 600).into());
+}
+
+#[test]
+fn unstake_cannot_exceed_share_token_supply() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .unstake(OptionalValue::Some(BigUint::from(STAKE_AMOUNT + 1)))
+        .single_esdt(
+            &SHARE_TOKEN.to_token_identifier(),
+            1,
+            &BigUint::from(STAKE_AMOUNT),
+        )
+        .with_result(ExpectError(4, as_str(ERROR_UNSTAKE_AMOUNT_EXCEEDS)))
+        .run();
+}
+
+#[test]
+fn stake_rejects_two_staking_transfers() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .stake(FARM_ID, OptionalValue::<u64>::None)
+        .esdt(TestEsdtTransfer(STAKED_TOKEN, 0, STAKE_AMOUNT))
+        .esdt(TestEsdtTransfer(STAKED_TOKEN, 0, STAKE_AMOUNT))
+        .with_result(ExpectError(
+            4,
+            as_str(ERROR_ONLY_ONE_STAKING_TRANSFER_ALLOWED),
+        ))
+        .run();
+}
+
+#[test]
+fn stake_rejects_unrelated_tokens() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .stake(FARM_ID, OptionalValue::<u64>::None)
+        .esdt(TestEsdtTransfer(STAKED_TOKEN, 0, STAKE_AMOUNT))
+        .esdt(TestEsdtTransfer(SECOND_STAKED_TOKEN, 0, STAKE_AMOUNT))
+        .with_result(ExpectError(4, as_str(ERROR_INVALID_SHARE_TOKEN)))
+        .run();
+}
+
+#[test]
+fn stake_requires_staking_transfer() {
+    let mut state = TokenStakingTestState::new();
+
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .stake(FARM_ID, OptionalValue::<u64>::None)
+        .single_esdt(
+            &SHARE_TOKEN.to_token_identifier(),
+            1,
+            &BigUint::from(STAKE_AMOUNT),
+        )
+        .with_result(ExpectError(4, as_str(ERROR_STAKED_TOKEN_MISSING)))
+        .run();
+}
+
+#[test]
+fn claim_and_unstake_reject_foreign_tokens() {
+    let mut state = TokenStakingTestState::new();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .claim_rewards_endpoint(OptionalValue::<bool>::None)
+        .single_esdt(
+            &STAKED_TOKEN.to_token_identifier(),
+            0,
+            &BigUint::from(STAKE_AMOUNT),
+        )
+        .with_result(ExpectError(4, as_str(ERROR_INVALID_SHARE_TOKEN)))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .unstake(OptionalValue::<BigUint<StaticApi>>::None)
+        .with_result(ExpectError(4, as_str(ERROR_INVALID_SHARE_TOKEN)))
+        .run();
+}
@@ -0,0 +1,199 @@
+mod setup;
+
+use errors::{ERROR_ACTION_NOT_FOUND, ERROR_ACTION_NOT_READY, ERROR_ALREADY_ENDED};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+use structs::{EarlyUnstakePenalty, PenaltyDestination, PenaltySource, TimelockAction};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const DELAY_MS: u64 = 20_000;
+
+#[test]
+fn queued_rate_change_applies_after_delay() {
+    let mut state = TokenStakingTestState::new();
+    state.set_timelock_delay(DELAY_MS);
+    state.stake(USER, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS);
+    state.modify_rewards(REWARD_PER_SEC * 2, REWARD_PER_SHARE);
+
+    let pending_actions = state.get_pending_actions();
+    assert_eq!(pending_actions.len(), 1);
+    assert_eq!(pending_actions[0].id, 1);
+    assert_eq!(pending_actions[0].proposer, OWNER.to_managed_address());
+    assert_eq!(pending_actions[0].eta_ms, START_TS_MS + DELAY_MS);
+    assert_eq!(
+        pending_actions[0].action,
+        TimelockAction::ModifyRewards {
+            farm_id: FARM_ID,
+            reward_token: EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN.to_token_identifier()),
+            new_reward_per_sec: (REWARD_PER_SEC * 2).into(),
+            new_reward_per_share: REWARD_PER_SHARE.into(),
+        }
+    );
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).reward_per_sec,
+        REWARD_PER_SEC
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + DELAY_MS - 1_000);
+    state.execute_action_expect_error(USER, 1, ERROR_ACTION_NOT_READY);
+
+    state.set_block_timestamp_ms(START_TS_MS + DELAY_MS);
+    state.execute_action(USER, 1);
+    assert!(state.get_pending_actions().is_empty());
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).reward_per_sec,
+        REWARD_PER_SEC * 2
+    );
+
+    state.set_block_timestamp_ms(START_TS_MS + DELAY_MS + 10_000);
+    state.claim_rewards(USER, 1, STAKE_AMOUNT);
+    state.check_reward_balance(
+        USER,
+        expected_rewards(STAKE_AMOUNT, DELAY_MS / 1_000) + expected_rewards(STAKE_AMOUNT, 10) * 2,
+    );
+
+    state.execute_action_expect_error(USER, 1, ERROR_ACTION_NOT_FOUND);
+}
+
+#[test]
+fn owner_cancels_queued_withdrawal() {
+    let mut state = TokenStakingTestState::new();
+    state.set_timelock_delay(DELAY_MS);
+
+    state.withdraw_reward_token(REWARD_TOKEN, 1_000);
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).rewards_reserve,
+        INITIAL_REWARD_RESERVE
+    );
+
+    state
+        .world
+        .tx()
+        .from(USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .cancel_action(1u64)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .cancel_action(1u64)
+        .run();
+    assert!(state.get_pending_actions().is_empty());
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + DELAY_MS);
+    state.execute_action_expect_error(OWNER, 1, ERROR_ACTION_NOT_FOUND);
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).rewards_reserve,
+        INITIAL_REWARD_RESERVE
+    );
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .cancel_action(1u64)
+        .with_result(ExpectError(4, as_str(ERROR_ACTION_NOT_FOUND)))
+        .run();
+}
+
+#[test]
+fn queued_withdrawal_is_sent_to_owner_after_delay() {
+    let mut state = TokenStakingTestState::new();
+    state.set_timelock_delay(DELAY_MS);
+
+    state.withdraw_reward_token(REWARD_TOKEN, 1_000);
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + DELAY_MS);
+    state.execute_action(USER, 1);
+
+    assert_eq!(
+        state.get_farm_info().rewards.get(0).rewards_reserve,
+        INITIAL_REWARD_RESERVE - 1_000
+    );
+    state
+        .world
+        .check_account(OWNER)
+        .esdt_balance(REWARD_TOKEN, INITIAL_REWARD_RESERVE * 9 + 1_000);
+}
+
+#[test]
+fn queued_action_is_validated_on_execution() {
+    let mut state = TokenStakingTestState::new();
+    state.set_timelock_delay(DELAY_MS);
+
+    state.set_block_timestamp_ms(END_TS_MS - 10_000);
+    state.modify_end_ts(END_TS_MS + 50_000);
+    state.modify_end_ts(END_TS_MS + 100_000);
+
+    state.set_block_timestamp_ms(END_TS_MS + 10_000);
+    state.execute_action_expect_error(USER, 2, ERROR_ALREADY_ENDED);
+    assert_eq!(state.get_pending_actions().len(), 2);
+
+    state.set_timelock_delay(0);
+    state.set_block_timestamp_ms(END_TS_MS - 10_000 + DELAY_MS / 2);
+    state.execute_action_expect_error(USER, 1, ERROR_ACTION_NOT_READY);
+}
+
+#[test]
+fn early_unstake_penalty_change_is_queued() {
+    let mut state = TokenStakingTestState::new();
+    state.set_timelock_delay(DELAY_MS);
+
+    state.set_early_unstake_penalty(1_000, PenaltySource::Rewards, PenaltyDestination::Burn);
+
+    let pending_actions = state.get_pending_actions();
+    assert_eq!(pending_actions.len(), 1);
+    assert_eq!(
+        pending_actions[0].action,
+        TimelockAction::SetEarlyUnstakePenalty {
+            farm_id: FARM_ID,
+            penalty: EarlyUnstakePenalty {
+                penalty_bps: 1_000,
+                source: PenaltySource::Rewards,
+                destination: PenaltyDestination::Burn,
+            },
+        }
+    );
+    assert!(state.get_early_unstake_penalty_config().is_none());
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + DELAY_MS);
+    state.execute_action(USER, 1);
+    assert_eq!(
+        state
+            .get_early_unstake_penalty_config()
+            .map(|penalty| penalty.penalty_bps),
+        Some(1_000)
+    );
+}
+
+#[test]
+fn shorter_delay_waits_for_the_current_one() {
+    let mut state = TokenStakingTestState::new();
+    state.set_timelock_delay(DELAY_MS);
+
+    state.set_timelock_delay(0);
+    assert_eq!(state.get_timelock_delay(), DELAY_MS);
+    assert_eq!(
+        state.get_pending_actions()[0].action,
+        TimelockAction::SetTimelockDelay { delay_ms: 0 }
+    );
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + DELAY_MS / 2);
+    state.execute_action_expect_error(USER, 1, ERROR_ACTION_NOT_READY);
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + DELAY_MS);
+    state.execute_action(USER, 1);
+    assert_eq!(state.get_timelock_delay(), 0);
+
+    state.set_timelock_delay(DELAY_MS * 2);
+    assert_eq!(state.get_timelock_delay(), DELAY_MS * 2);
+    assert!(state.get_pending_actions().is_empty());
+}
@@ -0,0 +1,178 @@
+mod setup;
+
+use errors::{ERROR_DESTINATION_NOT_WHITELISTED, ERROR_NOT_OWNER};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const TREASURY: TestAddress = TestAddress::new("treasury");
+
+const DELAY_MS: u64 = 20_000;
+
+fn withdraw_to(state: &mut TokenStakingTestState, amount: u64, to: TestAddress) {
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_rewards(
+            FARM_ID,
+            BigUint::from(amount),
+            OptionalValue::Some(EgldOrEsdtTokenIdentifier::esdt(
+                REWARD_TOKEN.to_token_identifier(),
+            )),
+            OptionalValue::Some(to.to_managed_address()),
+        )
+        .run();
+}
+
+fn withdraw_to_expect_error(
+    state: &mut TokenStakingTestState,
+    amount: u64,
+    to: TestAddress,
+    error: &[u8],
+) {
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .withdraw_rewards(
+            FARM_ID,
+            BigUint::from(amount),
+            OptionalValue::Some(EgldOrEsdtTokenIdentifier::esdt(
+                REWARD_TOKEN.to_token_identifier(),
+            )),
+            OptionalValue::Some(to.to_managed_address()),
+        )
+        .with_result(ExpectError(4, as_str(error)))
+        .run();
+}
+
+fn get_treasury(state: &mut TokenStakingTestState) -> Address {
+    state
+        .world
+        .query()
+        .to(SC)
+        .typed(TemplateProxy)
+        .get_treasury()
+        .returns(ReturnsResultUnmanaged)
+        .run()
+}
+
+#[test]
+fn withdrawals_go_to_treasury_once_set() {
+    let mut state = TokenStakingTestState::new();
+    state.world.account(TREASURY).nonce(1);
+
+    assert_eq!(get_treasury(&mut state), OWNER.to_address());
+
+    state
+        .world
+        .tx()
+        .from(ADMIN)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_treasury(TREASURY.to_address())
+        .with_result(ExpectError(4, as_str(ERROR_NOT_OWNER)))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_treasury(TREASURY.to_address())
+        .run();
+    assert_eq!(get_treasury(&mut state), TREASURY.to_address());
+
+    state.withdraw_reward_token(REWARD_TOKEN, 1_000);
+    state
+        .world
+        .check_account(TREASURY)
+        .esdt_balance(REWARD_TOKEN, 1_000u64);
+    state
+        .world
+        .check_account(OWNER)
+        .esdt_balance(REWARD_TOKEN, INITIAL_REWARD_RESERVE * 9);
+}
+
+#[test]
+fn explicit_destination_must_be_whitelisted() {
+    let mut state = TokenStakingTestState::new();
+
+    withdraw_to_expect_error(&mut state, 1_000, ADMIN, ERROR_DESTINATION_NOT_WHITELISTED);
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .add_withdrawal_destinations(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .run();
+    withdraw_to(&mut state, 1_000, ADMIN);
+    state
+        .world
+        .check_account(ADMIN)
+        .esdt_balance(REWARD_TOKEN, 1_000u64);
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .remove_withdrawal_destinations(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .run();
+    withdraw_to_expect_error(&mut state, 1_000, ADMIN, ERROR_DESTINATION_NOT_WHITELISTED);
+}
+
+#[test]
+fn queued_withdrawals_keep_their_treasury_and_recheck_the_whitelist() {
+    let mut state = TokenStakingTestState::new();
+    state.world.account(TREASURY).nonce(1);
+    state.set_timelock_delay(DELAY_MS);
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .add_withdrawal_destinations(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .run();
+
+    state.withdraw_reward_token(REWARD_TOKEN, 1_000);
+    withdraw_to(&mut state, 1_000, ADMIN);
+
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .set_treasury(TREASURY.to_address())
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .remove_withdrawal_destinations(MultiValueVec::from(vec![ADMIN.to_address()]))
+        .run();
+
+    state.set_block_timestamp_ms(DEPLOY_TS_MS + DELAY_MS);
+    state.execute_action(USER, 1);
+    state
+        .world
+        .check_account(OWNER)
+        .esdt_balance(REWARD_TOKEN, INITIAL_REWARD_RESERVE * 9 + 1_000);
+    state
+        .world
+        .check_account(TREASURY)
+        .esdt_balance(REWARD_TOKEN, 0u64);
+
+    state.execute_action_expect_error(USER, 2, ERROR_DESTINATION_NOT_WHITELISTED);
+}
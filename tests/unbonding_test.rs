@@ -0,0 +1,102 @@
+mod setup;
+
+use errors::{ERROR_NOTHING_TO_WITHDRAW, ERROR_UNBONDING_NOT_FOUND};
+use multiversx_sc_scenario::imports::*;
+use setup::{tokenstaking_proxy::TemplateProxy, *};
+
+const STAKE_AMOUNT: u64 = 1_000;
+
+const UNBONDING_PERIOD_MS: u64 = 30_000;
+
+fn new_state_with_unbonding() -> TokenStakingTestState {
+    let mut state = TokenStakingTestState::new();
+    state.set_unbonding_period(UNBONDING_PERIOD_MS);
+    state.stake(USER, STAKE_AMOUNT);
+
+    state
+}
+
+#[test]
+fn unstake_starts_unbonding_instead_of_sending_tokens() {
+    let mut state = new_state_with_unbonding();
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT);
+    state.check_reward_balance(USER, expected_rewards(STAKE_AMOUNT, 20));
+    assert_eq!(state.get_total_staked(), 0u64.into());
+
+    let unbondings = state.get_unbondings(USER);
+    assert_eq!(unbondings.len(), 1);
+    assert_eq!(unbondings[0].id, 1);
+    assert_eq!(unbondings[0].amount, STAKE_AMOUNT);
+    assert_eq!(
+        unbondings[0].unlock_ts_ms,
+        START_TS_MS + 20_000 + UNBONDING_PERIOD_MS
+    );
+}
+
+#[test]
+fn withdraw_pays_only_unlocked_unbondings() {
+    let mut state = new_state_with_unbonding();
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000);
+    state.unstake_partial(USER, 1, STAKE_AMOUNT, 400);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT - 400);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000 + UNBONDING_PERIOD_MS - 1);
+    state.withdraw_expect_error(USER, ERROR_NOTHING_TO_WITHDRAW);
+
+    state.set_block_timestamp_ms(START_TS_MS + 10_000 + UNBONDING_PERIOD_MS);
+    state.withdraw(USER);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE - STAKE_AMOUNT + 400);
+    assert_eq!(state.get_unbondings(USER).len(), 1);
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000 + UNBONDING_PERIOD_MS);
+    state.withdraw(USER);
+    state.check_staked_balance(USER, INITIAL_STAKED_BALANCE);
+    assert!(state.get_unbondings(USER).is_empty());
+}
+
+#[test]
+fn cancelled_unbonding_is_staked_again() {
+    let mut state = new_state_with_unbonding();
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state.set_block_timestamp_ms(START_TS_MS + 30_000);
+    state.cancel_unbonding(USER, 1);
+
+    assert!(state.get_unbondings(USER).is_empty());
+    assert_eq!(state.get_total_staked(), STAKE_AMOUNT.into());
+    state.check_share_token(USER, 2, STAKE_AMOUNT, START_TS_MS + 30_000);
+
+    state.set_block_timestamp_ms(START_TS_MS + 40_000);
+    state.claim_rewards(USER, 2, STAKE_AMOUNT);
+    state.check_reward_balance(
+        USER,
+        expected_rewards(STAKE_AMOUNT, 20) + expected_rewards(STAKE_AMOUNT, 10),
+    );
+}
+
+#[test]
+fn unbonding_cannot_be_cancelled_by_another_address() {
+    let mut state = new_state_with_unbonding();
+
+    state.set_block_timestamp_ms(START_TS_MS + 20_000);
+    state.unstake(USER, 1, STAKE_AMOUNT);
+
+    state
+        .world
+        .tx()
+        .from(OTHER_USER)
+        .to(SC)
+        .typed(TemplateProxy)
+        .cancel_unbonding(FARM_ID, 1u64, OptionalValue::<u64>::None)
+        .with_result(ExpectError(4, as_str(ERROR_UNBONDING_NOT_FOUND)))
+        .run();
+}
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           18
+// Endpoints:                           93
 // Async Callback:                       1
-// Total number of exported functions:  21
+// Total number of exported functions:  96
 
 #![no_std]
 
@@ -20,24 +20,99 @@ multiversx_sc_wasm_adapter::endpoints! {
     (
         init => init
         upgrade => upgrade
+        proposeOwner => propose_owner
+        cancelOwnershipTransfer => cancel_ownership_transfer
+        acceptOwnership => accept_ownership
         addAdmins => add_admin
         removeAdmins => remove_admin
+        grantRole => grant_role
+        revokeRole => revoke_role
+        renounceAdmin => renounce_admin
+        renounceRole => renounce_role
+        setApprovalThreshold => set_approval_threshold
+        getOwner => get_owner
         isAdmin => is_admin
+        hasRole => has_role
+        getPendingOwner => get_pending_owner
         getAdmins => admins
+        getRoleMembers => role_members
+        getApprovalThreshold => approval_threshold
+        getActionApprovers => action_approvers
         pause => pause
         unpause => unpause
+        enableEmergencyMode => enable_emergency_mode
+        disableEmergencyMode => disable_emergency_mode
         isPaused => is_paused
+        isEmergencyMode => emergency_mode
+        setTimelockDelay => set_timelock_delay
+        cancelAction => cancel_action
+        getPendingActions => get_pending_actions
+        getTimelockDelay => timelock_delay_ms
         createFarm => create_farm
+        addRewardToken => add_reward_token
         modifyStartTs => modify_start_ts
         modifyEndTs => modify_end_ts
         modifyRewards => modify_rewards
         depositRewards => deposit_rewards
         withdrawRewards => withdraw_rewards
+        executeAction => execute_action
+        approveAction => approve_action
+        setTreasury => set_treasury
+        addWithdrawalDestinations => add_withdrawal_destinations
+        removeWithdrawalDestinations => remove_withdrawal_destinations
+        setSolvencyCheck => set_solvency_check
         getFarmInfo => get_farm_info
+        getFarms => get_farms
+        getTreasury => get_treasury
+        getFarmCount => get_farm_count
+        getRewardInfo => get_reward_info
+        getRateHistory => get_rate_history
+        getAccruedRewards => get_accrued_rewards_view
+        getProjectedObligations => get_projected_obligations_view
+        getRewardsRunway => get_rewards_runway
+        getLastFarmId => last_farm_id
+        getFarmIdByShareToken => share_token_farm_id
+        getRewardTokens => reward_tokens
+        getTotalStaked => total_staked
+        getTotalShareSupply => total_share_supply
+        getBoostedShareSupply => boosted_share_supply
+        getWithdrawalDestinations => withdrawal_destinations
+        isSolvencyCheckEnabled => solvency_check_enabled
+        getUserStaked => user_staked
+        getRewardFee => reward_fee_bps
+        getStakeFee => stake_fee_bps
+        setProtocolFees => set_protocol_fees
+        setFeeCollector => set_fee_collector
+        withdrawFees => withdraw_fees
+        getFeeCollector => get_fee_collector
+        getAccruedFees => get_accrued_fees
+        setLockTier => set_lock_tier
+        removeLockTier => remove_lock_tier
+        setMinStakeDuration => set_min_stake_duration
+        getLockTiers => get_lock_tiers
+        getMinStakeDuration => get_min_stake_duration
         stake => stake
+        stakeFor => stake_for
         unstake => unstake
+        unstakeTo => unstake_to
+        emergencyUnstake => emergency_unstake
+        cancelUnbonding => cancel_unbonding
         claimRewards => claim_rewards_endpoint
+        claimRewardsTo => claim_rewards_to
+        compound => compound
+        claimUnpaidRewards => claim_unpaid_rewards
+        settleExpiredBoost => settle_expired_boost_endpoint
         getClaimableRewards => get_claimable_rewards
+        getUnpaidRewards => get_unpaid_rewards
+        setEarlyUnstakePenalty => set_early_unstake_penalty
+        removeEarlyUnstakePenalty => remove_early_unstake_penalty
+        getEarlyUnstakePenaltyConfig => get_early_unstake_penalty_config
+        getEarlyUnstakePenalty => get_early_unstake_penalty
+        setUnbondingPeriod => set_unbonding_period
+        withdraw => withdraw
+        getUnbondingPeriod => get_unbonding_period
+        getUnbondings => get_unbondings
+        getTotalUnbonding => get_total_unbonding
     )
 }
 